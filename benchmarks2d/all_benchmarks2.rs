@@ -17,6 +17,7 @@ mod joint_ball2;
 mod joint_fixed2;
 mod joint_prismatic2;
 mod pyramid2;
+mod voxels2;
 
 fn demo_name_from_command_line() -> Option<String> {
     let mut args = std::env::args();
@@ -57,6 +58,8 @@ pub fn main() {
         ("Convex polygons", convex_polygons2::init_world),
         ("Heightfield", heightfield2::init_world),
         ("Pyramid", pyramid2::init_world),
+        ("Voxels (SAP)", voxels2::init_world_with_sap),
+        ("Voxels (grid)", voxels2::init_world_with_grid),
         ("(Stress test) joint ball", joint_ball2::init_world),
         ("(Stress test) joint fixed", joint_fixed2::init_world),
         (
@@ -0,0 +1,64 @@
+use rapier2d::prelude::*;
+use rapier_testbed2d::Testbed;
+
+pub fn init_world_with_sap(testbed: &mut Testbed) {
+    init_world(testbed, false)
+}
+
+pub fn init_world_with_grid(testbed: &mut Testbed) {
+    init_world(testbed, true)
+}
+
+fn init_world(testbed: &mut Testbed, use_grid: bool) {
+    /*
+     * World
+     */
+    let mut bodies = RigidBodySet::new();
+    let mut colliders = ColliderSet::new();
+    let impulse_joints = ImpulseJointSet::new();
+    let multibody_joints = MultibodyJointSet::new();
+
+    /*
+     * Ground
+     */
+    let ground_size = 50.0;
+
+    let rigid_body = RigidBodyBuilder::fixed();
+    let handle = bodies.insert(rigid_body);
+    let collider = ColliderBuilder::cuboid(ground_size, 1.2);
+    colliders.insert_with_parent(collider, handle, &mut bodies);
+
+    /*
+     * Create a 50x50 grid of voxels.
+     */
+    let num = 50;
+    let rad = 0.5;
+
+    let shift = rad * 2.0 + 0.01;
+    let centerx = shift * (num as f32) / 2.0;
+
+    for i in 0..num {
+        for j in 0..num {
+            let x = i as f32 * shift - centerx;
+            let y = j as f32 * shift * 1.5 + rad + 2.0;
+
+            // Build the rigid body.
+            let rigid_body = RigidBodyBuilder::dynamic().translation(vector![x, y]);
+            let handle = bodies.insert(rigid_body);
+            let collider = ColliderBuilder::cuboid(rad, rad);
+            colliders.insert_with_parent(collider, handle, &mut bodies);
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    testbed.set_world(bodies, colliders, impulse_joints, multibody_joints);
+
+    if use_grid {
+        // The cells are as wide as the voxels.
+        testbed.physics_state_mut().broad_phase_grid = Some(BroadPhaseGrid::new(rad * 2.0));
+    }
+
+    testbed.look_at(point![0.0, 40.0], 10.0);
+}
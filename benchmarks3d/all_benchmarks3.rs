@@ -23,6 +23,7 @@ mod keva3;
 mod pyramid3;
 mod stacks3;
 mod trimesh3;
+mod voxels3;
 
 enum Command {
     Run(String),
@@ -57,6 +58,8 @@ pub fn main() {
         ("Heightfield", heightfield3::init_world),
         ("Stacks", stacks3::init_world),
         ("Pyramid", pyramid3::init_world),
+        ("Voxels (SAP)", voxels3::init_world_with_sap),
+        ("Voxels (grid)", voxels3::init_world_with_grid),
         ("Trimesh", trimesh3::init_world),
         ("ImpulseJoint ball", joint_ball3::init_world),
         ("ImpulseJoint fixed", joint_fixed3::init_world),
@@ -10,6 +10,10 @@ pub struct CollisionDetectionCounters {
     pub broad_phase_time: Timer,
     /// Time spent for the narrow-phase of the collision detection.
     pub narrow_phase_time: Timer,
+    /// Time spent for computing the contacts between non-sensor colliders, as part of the narrow-phase.
+    pub contacts_time: Timer,
+    /// Time spent for computing the intersections involving sensors, as part of the narrow-phase.
+    pub intersections_time: Timer,
 }
 
 impl CollisionDetectionCounters {
@@ -19,6 +23,8 @@ impl CollisionDetectionCounters {
             ncontact_pairs: 0,
             broad_phase_time: Timer::new(),
             narrow_phase_time: Timer::new(),
+            contacts_time: Timer::new(),
+            intersections_time: Timer::new(),
         }
     }
 
@@ -27,6 +33,8 @@ impl CollisionDetectionCounters {
         self.ncontact_pairs = 0;
         self.broad_phase_time.reset();
         self.narrow_phase_time.reset();
+        self.contacts_time.reset();
+        self.intersections_time.reset();
     }
 }
 
@@ -34,6 +42,8 @@ impl Display for CollisionDetectionCounters {
     fn fmt(&self, f: &mut Formatter) -> Result {
         writeln!(f, "Number of contact pairs: {}", self.ncontact_pairs)?;
         writeln!(f, "Broad-phase time: {}", self.broad_phase_time)?;
-        writeln!(f, "Narrow-phase time: {}", self.narrow_phase_time)
+        writeln!(f, "Narrow-phase time: {}", self.narrow_phase_time)?;
+        writeln!(f, "Contacts time: {}", self.contacts_time)?;
+        writeln!(f, "Intersections time: {}", self.intersections_time)
     }
 }
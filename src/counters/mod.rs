@@ -1,5 +1,6 @@
 //! Counters for benchmarking various parts of the physics engine.
 
+use crate::math::Real;
 use std::fmt::{Display, Formatter, Result};
 
 pub use self::ccd_counters::CCDCounters;
@@ -110,6 +111,24 @@ impl Counters {
         self.solver.ncontacts = n;
     }
 
+    /// The largest change of a body velocity performed by the last velocity iteration
+    /// of the constraints solver, during the last timestep.
+    ///
+    /// This measures how far the velocity constraints were from convergence. This is only
+    /// computed by the sequential solver, and if the counters are enabled.
+    pub fn velocity_residual(&self) -> Real {
+        self.solver.velocity_residual
+    }
+
+    /// The largest penetration depth, beyond `IntegrationParameters::allowed_linear_error`,
+    /// remaining after the constraints resolution of the last timestep.
+    ///
+    /// This is predicted from the contacts and the velocities computed by the solver. This is
+    /// only computed by the sequential solver, and if the counters are enabled.
+    pub fn position_residual(&self) -> Real {
+        self.solver.position_residual
+    }
+
     /// Set the number of contact pairs generated.
     pub fn set_ncontact_pairs(&mut self, n: usize) {
         self.cd.ncontact_pairs = n;
@@ -219,6 +238,18 @@ measure_method!(
     narrow_phase_time,
     cd.narrow_phase_time
 );
+measure_method!(
+    contacts_started,
+    contacts_completed,
+    contacts_time,
+    cd.contacts_time
+);
+measure_method!(
+    intersections_started,
+    intersections_completed,
+    intersections_time,
+    cd.intersections_time
+);
 
 impl Display for Counters {
     fn fmt(&self, f: &mut Formatter) -> Result {
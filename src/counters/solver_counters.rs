@@ -1,4 +1,5 @@
 use crate::counters::Timer;
+use crate::math::Real;
 use std::fmt::{Display, Formatter, Result};
 
 /// Performance counters related to constraints resolution.
@@ -18,6 +19,12 @@ pub struct SolverCounters {
     pub position_assembly_time: Timer,
     /// Time spent for the update of the position of the bodies.
     pub position_resolution_time: Timer,
+    /// The largest change of a body velocity (linear or angular) performed by the last
+    /// velocity iteration.
+    pub velocity_residual: Real,
+    /// The largest penetration depth exceeding the allowed linear error, predicted from the
+    /// velocities resulting from the constraints resolution.
+    pub position_residual: Real,
 }
 
 impl SolverCounters {
@@ -31,6 +38,8 @@ impl SolverCounters {
             velocity_update_time: Timer::new(),
             position_assembly_time: Timer::new(),
             position_resolution_time: Timer::new(),
+            velocity_residual: 0.0,
+            position_residual: 0.0,
         }
     }
 
@@ -43,6 +52,8 @@ impl SolverCounters {
         self.velocity_update_time.reset();
         self.position_assembly_time.reset();
         self.position_resolution_time.reset();
+        self.velocity_residual = 0.0;
+        self.position_residual = 0.0;
     }
 }
 
@@ -62,6 +73,8 @@ impl Display for SolverCounters {
             f,
             "Position resolution time: {}",
             self.position_resolution_time
-        )
+        )?;
+        writeln!(f, "Velocity residual: {}", self.velocity_residual)?;
+        writeln!(f, "Position residual: {}", self.position_residual)
     }
 }
@@ -1,12 +1,13 @@
 use super::TOIEntry;
 use crate::dynamics::{IslandManager, RigidBodyHandle, RigidBodySet};
 use crate::geometry::{ColliderParent, ColliderSet, CollisionEvent, NarrowPhase};
-use crate::math::Real;
+use crate::math::{Point, Real, Vector};
 use crate::parry::utils::SortedPair;
 use crate::pipeline::{EventHandler, QueryPipeline, QueryPipelineMode};
 use crate::prelude::{ActiveEvents, CollisionEventFlags};
-use parry::query::{DefaultQueryDispatcher, QueryDispatcher};
+use parry::query::{DefaultQueryDispatcher, NonlinearRigidMotion, QueryDispatcher};
 use parry::utils::hashmap::HashMap;
+use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
 pub enum PredictedImpacts {
@@ -21,6 +22,9 @@ pub enum PredictedImpacts {
 pub struct CCDSolver {
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     query_pipeline: QueryPipeline,
+    min_toi: Real,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    impact_points: HashMap<RigidBodyHandle, (Point<Real>, Vector<Real>)>,
 }
 
 impl Default for CCDSolver {
@@ -45,16 +49,46 @@ impl CCDSolver {
     {
         CCDSolver {
             query_pipeline: QueryPipeline::with_query_dispatcher(d),
+            min_toi: 0.0,
+            impact_points: HashMap::default(),
         }
     }
 
+    /// The smallest time-of-impact taken into account for splitting a timestep into substeps.
+    pub fn min_toi(&self) -> Real {
+        self.min_toi
+    }
+
+    /// Sets the smallest time-of-impact taken into account for splitting a timestep into substeps.
+    ///
+    /// Impacts happening sooner than `min_toi` after the beginning of a substep don't trigger
+    /// a new substep. They are handled by the contact solver instead. Increasing this value
+    /// reduces the number of substeps in scenes with many bodies in grazing contact, at the cost
+    /// of a higher risk of tunneling.
+    pub fn set_min_toi(&mut self, min_toi: Real) {
+        self.min_toi = min_toi;
+    }
+
     /// Apply motion-clamping to the bodies affected by the given `impacts`.
     ///
-    /// The `impacts` should be the result of a previous call to `self.predict_next_impacts`.
-    pub fn clamp_motions(&self, dt: Real, bodies: &mut RigidBodySet, impacts: &PredictedImpacts) {
+    /// The `impacts` should be the result of a previous call to `self.predict_impacts_at_next_positions`.
+    /// Returns, for each clamped rigid-body, the world-space point where it hit an obstacle, and the
+    /// contact normal at this point, pointing towards the clamped rigid-body.
+    pub fn clamp_motions(
+        &self,
+        dt: Real,
+        bodies: &mut RigidBodySet,
+        impacts: &PredictedImpacts,
+    ) -> Vec<(RigidBodyHandle, Point<Real>, Vector<Real>)> {
+        let mut clamped = vec![];
+
         match impacts {
             PredictedImpacts::Impacts(tois) => {
                 for (handle, toi) in tois {
+                    if let Some((point, normal)) = self.impact_points.get(handle) {
+                        clamped.push((*handle, *point, *normal));
+                    }
+
                     let rb = bodies.index_mut_internal(*handle);
                     let local_com = &rb.mprops.local_mprops.local_com;
 
@@ -79,6 +113,8 @@ impl CCDSolver {
             }
             _ => {}
         }
+
+        clamped
     }
 
     /// Updates the set of bodies that needs CCD to be resolved.
@@ -106,12 +142,50 @@ impl CCDSolver {
                 let moving_fast = rb.ccd.is_moving_fast(dt, &rb.integrated_vels, forces);
                 rb.ccd.ccd_active = moving_fast;
                 ccd_active = ccd_active || moving_fast;
+            } else {
+                // CCD may have been disabled since it was last activated.
+                rb.ccd.ccd_active = false;
             }
         }
 
         ccd_active
     }
 
+    /// Keeps the CCD-active flag only for the `max_active` fastest CCD-active bodies.
+    ///
+    /// The bodies are ranked by their maximum point velocity. This should be called after
+    /// `self.update_ccd_active_flags`. Returns `true` if any rigid-body remains CCD-active.
+    pub fn limit_ccd_active_bodies(
+        &self,
+        islands: &IslandManager,
+        bodies: &mut RigidBodySet,
+        max_active: usize,
+    ) -> bool {
+        let mut ccd_active_bodies: Vec<_> = islands
+            .active_dynamic_bodies()
+            .iter()
+            .filter_map(|handle| {
+                let rb = &bodies[*handle];
+                if rb.ccd.ccd_active {
+                    Some((rb.ccd.max_point_velocity(&rb.integrated_vels), *handle))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if ccd_active_bodies.len() > max_active {
+            // NOTE: use a stable sort so the result only depends on the active set order.
+            ccd_active_bodies.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+            for (_, handle) in &ccd_active_bodies[max_active..] {
+                bodies.index_mut_internal(*handle).ccd.ccd_active = false;
+            }
+        }
+
+        max_active > 0 && !ccd_active_bodies.is_empty()
+    }
+
     /// Find the first time a CCD-enabled body has a non-sensor collider hitting another non-sensor collider.
     pub fn find_first_impact(
         &mut self,
@@ -211,7 +285,9 @@ impl CCDSolver {
                                     min_toi,
                                     smallest_dist,
                                 ) {
-                                    min_toi = min_toi.min(toi.toi);
+                                    if toi.toi >= self.min_toi {
+                                        min_toi = min_toi.min(toi.toi);
+                                    }
                                 }
                             }
 
@@ -228,6 +304,75 @@ impl CCDSolver {
         }
     }
 
+    /// Computes the first time-of-impact between the colliders attached to two rigid-bodies.
+    ///
+    /// Both rigid-bodies are assumed to move from their current positions at their current linear
+    /// and angular velocities. Sensor colliders and colliders with incompatible collision groups are
+    /// ignored. Returns `None` if one of the rigid-bodies does not exist or if no impact happens before
+    /// `dt`, and `Some(0.0)` if the rigid-bodies are already penetrating.
+    pub fn predict_pair_impact(
+        &self,
+        dt: Real,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+    ) -> Option<Real> {
+        let rb1 = bodies.get(body1)?;
+        let rb2 = bodies.get(body2)?;
+        let motion1 = NonlinearRigidMotion::new(
+            rb1.pos.position,
+            rb1.mprops.local_mprops.local_com,
+            rb1.vels.linvel,
+            rb1.vels.angvel,
+        );
+        let motion2 = NonlinearRigidMotion::new(
+            rb2.pos.position,
+            rb2.mprops.local_mprops.local_com,
+            rb2.vels.linvel,
+            rb2.vels.angvel,
+        );
+        let mut min_toi = None;
+
+        for ch1 in &rb1.colliders.0 {
+            let co1 = &colliders[*ch1];
+
+            for ch2 in &rb2.colliders.0 {
+                let co2 = &colliders[*ch2];
+
+                if co1.is_sensor()
+                    || co2.is_sensor()
+                    || !co1.flags.collision_groups.test(co2.flags.collision_groups)
+                {
+                    continue;
+                }
+
+                let motion_c1 =
+                    motion1.prepend(co1.parent.map(|p| p.pos_wrt_parent).unwrap_or(co1.pos.0));
+                let motion_c2 =
+                    motion2.prepend(co2.parent.map(|p| p.pos_wrt_parent).unwrap_or(co2.pos.0));
+
+                if let Ok(Some(toi)) = self
+                    .query_pipeline
+                    .query_dispatcher()
+                    .nonlinear_time_of_impact(
+                        &motion_c1,
+                        co1.shape.as_ref(),
+                        &motion_c2,
+                        co2.shape.as_ref(),
+                        0.0,
+                        min_toi.unwrap_or(dt),
+                        true,
+                    )
+                {
+                    min_toi = Some(toi.toi);
+                }
+            }
+        }
+
+        min_toi
+    }
+
     /// Outputs the set of bodies as well as their first time-of-impact event.
     pub fn predict_impacts_at_next_positions(
         &mut self,
@@ -242,6 +387,7 @@ impl CCDSolver {
         let mut all_toi = BinaryHeap::new();
         let mut pairs_seen = HashMap::default();
         let mut min_overstep = dt;
+        self.impact_points.clear();
 
         // Update the query pipeline.
         self.query_pipeline.update_with_mode(
@@ -395,11 +541,17 @@ impl CCDSolver {
 
             if should_freeze1 {
                 let _ = frozen.insert(toi.b1.unwrap(), toi.toi);
+                let _ = self
+                    .impact_points
+                    .insert(toi.b1.unwrap(), (toi.witness1, toi.normal2));
                 colliders_to_check.extend_from_slice(&rb1.unwrap().colliders.0);
             }
 
             if should_freeze2 {
                 let _ = frozen.insert(toi.b2.unwrap(), toi.toi);
+                let _ = self
+                    .impact_points
+                    .insert(toi.b2.unwrap(), (toi.witness2, toi.normal1));
                 colliders_to_check.extend_from_slice(&rb2.unwrap().colliders.0);
             }
 
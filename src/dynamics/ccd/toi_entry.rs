@@ -1,6 +1,6 @@
 use crate::dynamics::{RigidBody, RigidBodyHandle};
 use crate::geometry::{Collider, ColliderHandle};
-use crate::math::Real;
+use crate::math::{Point, Real, Vector};
 use parry::query::{NonlinearRigidMotion, QueryDispatcher};
 
 #[derive(Copy, Clone, Debug)]
@@ -14,6 +14,14 @@ pub struct TOIEntry {
     // includes colliders pairs with mismatching solver_groups.
     pub is_pseudo_intersection_test: bool,
     pub timestamp: usize,
+    /// The world-space impact point on the first collider.
+    pub witness1: Point<Real>,
+    /// The world-space impact point on the second collider.
+    pub witness2: Point<Real>,
+    /// The world-space outward normal of the first collider at the impact point.
+    pub normal1: Vector<Real>,
+    /// The world-space outward normal of the second collider at the impact point.
+    pub normal2: Vector<Real>,
 }
 
 impl TOIEntry {
@@ -25,6 +33,10 @@ impl TOIEntry {
         b2: Option<RigidBodyHandle>,
         is_pseudo_intersection_test: bool,
         timestamp: usize,
+        witness1: Point<Real>,
+        witness2: Point<Real>,
+        normal1: Vector<Real>,
+        normal2: Vector<Real>,
     ) -> Self {
         Self {
             toi,
@@ -34,6 +46,10 @@ impl TOIEntry {
             b2,
             is_pseudo_intersection_test,
             timestamp,
+            witness1,
+            witness2,
+            normal1,
+            normal2,
         }
     }
 
@@ -141,6 +157,8 @@ impl TOIEntry {
             .ok();
 
         let toi = res_toi??;
+        let pos1 = motion_c1.position_at_time(toi.toi);
+        let pos2 = motion_c2.position_at_time(toi.toi);
 
         Some(Self::new(
             toi.toi,
@@ -150,6 +168,10 @@ impl TOIEntry {
             co2.parent.map(|p| p.handle),
             is_pseudo_intersection_test,
             0,
+            pos1 * toi.witness1,
+            pos2 * toi.witness2,
+            pos1 * toi.normal1.into_inner(),
+            pos2 * toi.normal2.into_inner(),
         ))
     }
 
@@ -1,6 +1,34 @@
 use crate::math::Real;
 
+/// The order in which the joint and contact constraints are solved by each solver iteration.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum SolveOrder {
+    /// The joint constraints are solved before the contact constraints.
+    JointsFirst,
+    /// The contact constraints are solved before the joint constraints.
+    ContactsFirst,
+    /// The joint constraints are solved first on even iterations, and last on odd iterations.
+    Interleaved,
+}
+
+impl SolveOrder {
+    /// Should the joints be solved before the contacts during the given solver iteration?
+    pub(crate) fn joints_first(self, iteration: usize) -> bool {
+        match self {
+            SolveOrder::JointsFirst => true,
+            SolveOrder::ContactsFirst => false,
+            SolveOrder::Interleaved => iteration % 2 == 0,
+        }
+    }
+}
+
 /// Parameters for a time-step of the physics engine.
+///
+/// The rigid-bodies are integrated with a semi-implicit (symplectic) Euler scheme: the velocities
+/// are updated first, from the forces and the constraints, and the new velocities are then used
+/// to update the positions. This scheme keeps the energy of conservative systems bounded instead
+/// of letting it drift over time.
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct IntegrationParameters {
@@ -21,11 +49,17 @@ pub struct IntegrationParameters {
     /// 0-1: multiplier for how much of the constraint violation (e.g. contact penetration)
     /// will be compensated for during the velocity solve.
     /// (default `0.8`).
+    ///
+    /// This is the error reduction parameter (ERP) of the contact constraints. Joints use
+    /// `joint_erp` instead.
     pub erp: Real,
     /// 0-1: the damping ratio used by the springs for Baumgarte constraints stabilization.
     /// Lower values make the constraints more compliant (more "springy", allowing more visible penetrations
     /// before stabilization).
     /// (default `0.25`).
+    ///
+    /// Together with `erp`, this determines the constraint force mixing (CFM) of the contact
+    /// constraints, see `Self::cfm_factor`. Joints use `joint_damping_ratio` instead.
     pub damping_ratio: Real,
 
     /// 0-1: multiplier for how much of the joint violation
@@ -39,10 +73,38 @@ pub struct IntegrationParameters {
 
     /// Amount of penetration the engine wont attempt to correct (default: `0.001m`).
     pub allowed_linear_error: Real,
-    /// Maximum amount of penetration the solver will attempt to resolve in one timestep.
+    /// Maximum amount of penetration the solver will attempt to resolve in one timestep (default: `Real::MAX`).
+    ///
+    /// This caps the correction velocity of each contact to `erp * max_penetration_correction / dt`,
+    /// so deeply penetrating bodies (e.g. spawned overlapping) separate smoothly over several steps
+    /// instead of being pushed apart violently. The default value doesn't limit the correction.
     pub max_penetration_correction: Real,
+    /// Velocity components smaller than this threshold are set to zero after each velocity solve (default: `0.0`).
+    ///
+    /// This is applied to each component of the linear and angular velocities of the dynamic
+    /// rigid-bodies in persistent contact, i.e., with at least one contact that already existed
+    /// during the previous timestep. Free-flying bodies, and bodies that just started touching
+    /// something, are never snapped. A small positive value can be used to remove the tiny
+    /// residual velocities that make resting stacks wobble without ever falling asleep, at the
+    /// cost of some physical accuracy. The default value of zero disables velocity snapping.
+    pub velocity_snap_threshold: Real,
+    /// Relative normal velocity below which an impact doesn't bounce (default: `0.5`).
+    ///
+    /// Contacts with a relative normal velocity smaller than this threshold, in absolute value,
+    /// are solved as if their restitution coefficient was zero. This prevents objects with a
+    /// restitution coefficient from bouncing forever on the ground because of the velocity
+    /// gained from gravity during each timestep. Set it to zero to always apply restitution.
+    pub restitution_velocity_threshold: Real,
     /// The maximal distance separating two objects that will generate predictive contacts (default: `0.002`).
     pub prediction_distance: Real,
+    /// Extra distance two colliders must be separated by before a contact stop event is emitted (default: `0.0`).
+    ///
+    /// A contact start event is emitted as soon as two colliders are closer than the prediction
+    /// distance, but the matching stop event is only emitted once they are separated by more
+    /// than `prediction_distance + contact_event_hysteresis`. This avoids a flood of start/stop
+    /// events for colliders resting right at the prediction distance. It doesn't affect the
+    /// contacts seen by the constraints solver.
+    pub contact_event_hysteresis: Real,
     /// Maximum number of iterations performed to solve non-penetration and joint constraints (default: `4`).
     pub max_velocity_iterations: usize,
     /// Maximum number of iterations performed to solve friction constraints (default: `8`).
@@ -52,10 +114,83 @@ pub struct IntegrationParameters {
     /// If `false`, friction and non-penetration constraints will be solved in the same loop. Otherwise,
     /// non-penetration constraints are solved first, and friction constraints are solved after (default: `true`).
     pub interleave_restitution_and_friction_resolution: bool,
+    /// The order in which joint and contact constraints are solved (default: `SolveOrder::JointsFirst`).
+    ///
+    /// The constraints solved last tend to be better satisfied at the end of each step, so this
+    /// can affect the stability of mechanisms with both joints and contacts.
+    pub joint_contact_order: SolveOrder,
+    /// If `false`, the impulses cached from the previous timestep are discarded before the
+    /// constraints of the current timestep are solved (default: `true`).
+    ///
+    /// Disabling this generally costs some stability, but it can help determine whether a
+    /// jitter issue comes from stale cached impulses or from the constraint formulation itself.
+    pub warmstart: bool,
     /// Minimum number of dynamic bodies in each active island (default: `128`).
     pub min_island_size: usize,
+    /// The number of threads used to solve the islands when the `parallel` feature is enabled
+    /// (default: `None`).
+    ///
+    /// If `None` (or `Some(0)`), the islands are solved by rayon's global thread pool. Otherwise,
+    /// the physics pipeline creates its own thread pool with this number of threads, which is
+    /// useful to bound the number of threads used by each simulation when several of them run
+    /// in the same process.
+    ///
+    /// The islands don't share any mutable state, so the order in which they are solved, and
+    /// the number of threads, don't affect the results: the parallel solver is already
+    /// reproducible from one run to another.
+    /// This has no effect if the `parallel` feature is disabled.
+    pub num_threads: Option<usize>,
+    /// If `true`, the islands are solved sequentially by the same solver as when the `parallel`
+    /// feature is disabled, even if it is enabled (default: `false`).
+    ///
+    /// This allows switching at runtime between the multithreaded solver and the sequential one,
+    /// e.g., to get the same results as a build without the `parallel` feature for a networked game.
+    /// This has no effect if the `parallel` feature is disabled.
+    pub force_serial_solver: bool,
+    /// If `true`, the non-penetration constraints of contact manifolds with exactly two contacts
+    /// are solved simultaneously instead of one after the other (default: `false`).
+    ///
+    /// In 2D, this makes stacks of boxes converge faster and jitter less, at the cost of a
+    /// slightly more expensive resolution. In 3D, the contact manifolds between two boxes
+    /// generally have four contacts, so they are still solved one contact after the other.
+    /// This disables the SIMD resolution of contacts by the sequential solver, and only
+    /// affects the contacts solved without SIMD by the parallel solver.
+    pub use_block_solver: bool,
+    /// If `false`, the intersections between sensors and other colliders are not computed (default: `true`).
+    ///
+    /// Setting this to `false` when the scene doesn't contain any sensor skips the intersection
+    /// pass of the narrow-phase entirely. The time spent on this pass is measured by the
+    /// `intersections_time` counter. No intersection event is generated while this is `false`.
+    pub compute_intersections: bool,
     /// Maximum number of substeps performed by the  solver (default: `1`).
     pub max_ccd_substeps: usize,
+    /// Number of times the constraints solver runs during each timestep, or each CCD substep (default: `1`).
+    ///
+    /// The timestep is split into `num_solver_substeps` intervals of equal length, and the
+    /// velocity and position resolution is performed once for each interval against the same
+    /// contact manifolds: the collision-detection isn't executed between solver substeps.
+    /// Instead, the solver contacts are moved along with the rigid-bodies. This improves the
+    /// stability of stacks and fast collisions, at a lower cost than reducing `dt`.
+    ///
+    /// The islands and the external forces, including the ones applied by
+    /// `PhysicsHooks::on_apply_forces`, are computed only once before the first solver substep.
+    ///
+    /// If CCD is enabled, motion clamping is only applied to the last solver substep.
+    pub num_solver_substeps: usize,
+    /// Maximum number of CCD-active bodies allowed to split the timestep into substeps (default: `None`).
+    ///
+    /// If set, only the fastest CCD-active bodies (ranked by their maximum point velocity) are
+    /// taken into account for finding the next substep. The other CCD-active bodies are still
+    /// motion-clamped at the end of each substep, but they won't trigger additional substeps.
+    /// This bounds the cost of CCD in scenes with many fast-moving bodies. `None` means no limit.
+    pub max_ccd_substepping_bodies: Option<usize>,
+    /// If `true`, the positions and velocities of the active rigid-bodies are checked after each
+    /// substep, and `EventHandler::handle_invalid_body` is called for every rigid-body with a
+    /// NaN or infinite component (default: `false`).
+    ///
+    /// This is a debugging aid for finding the first body of an exploding simulation. Nothing is
+    /// checked when this is `false`.
+    pub validate: bool,
 }
 
 impl IntegrationParameters {
@@ -157,18 +292,30 @@ impl Default for IntegrationParameters {
             joint_damping_ratio: 1.0,
             allowed_linear_error: 0.001, // 0.005
             max_penetration_correction: Real::MAX,
+            velocity_snap_threshold: 0.0,
+            restitution_velocity_threshold: 0.5,
             prediction_distance: 0.002,
+            contact_event_hysteresis: 0.0,
             max_velocity_iterations: 4,
             max_velocity_friction_iterations: 8,
             max_stabilization_iterations: 1,
             interleave_restitution_and_friction_resolution: true, // Enabling this makes a big difference for 2D stability.
+            joint_contact_order: SolveOrder::JointsFirst,
+            warmstart: true,
             // TODO: what is the optimal value for min_island_size?
             // It should not be too big so that we don't end up with
             // huge islands that don't fit in cache.
             // However we don't want it to be too small and end up with
             // tons of islands, reducing SIMD parallelism opportunities.
             min_island_size: 128,
+            num_threads: None,
+            force_serial_solver: false,
+            use_block_solver: false,
+            compute_intersections: true,
             max_ccd_substeps: 1,
+            num_solver_substeps: 1,
+            max_ccd_substepping_bodies: None,
+            validate: false,
         }
     }
 }
@@ -4,6 +4,7 @@ use crate::dynamics::{
 };
 use crate::geometry::{ColliderSet, NarrowPhase};
 use crate::math::Real;
+use crate::pipeline::EventHandler;
 use crate::utils::WDot;
 
 /// Structure responsible for maintaining the set of active rigid-bodies, and
@@ -34,8 +35,13 @@ impl IslandManager {
         }
     }
 
-    pub(crate) fn num_islands(&self) -> usize {
-        self.active_islands.len() - 1
+    /// The number of active islands computed during the last timestep.
+    ///
+    /// An island is a set of awake dynamic rigid-bodies interacting with each other through
+    /// contacts or joints. Several small islands may be merged together to reach the
+    /// `IntegrationParameters::min_island_size`.
+    pub fn num_islands(&self) -> usize {
+        self.active_islands.len().saturating_sub(1)
     }
 
     /// Update this data-structure after one or multiple rigid-bodies have been removed for `bodies`.
@@ -86,7 +92,7 @@ impl IslandManager {
         }
     }
 
-    /// Forces the specified rigid-body to wake up if it is dynamic.
+    /// Forces the specified rigid-body to wake up if it is dynamic, enabled, and not frozen.
     ///
     /// If `strong` is `true` then it is assured that the rigid-body will
     /// remain awake during multiple subsequent timesteps.
@@ -94,7 +100,10 @@ impl IslandManager {
         // NOTE: the use an Option here because there are many legitimate cases (like when
         //       deleting a joint attached to an already-removed body) where we could be
         //       attempting to wake-up a rigid-body that has already been deleted.
-        if bodies.get(handle).map(|rb| rb.body_type()) == Some(RigidBodyType::Dynamic) {
+        if bodies.get(handle).map(|rb| {
+            rb.body_type() == RigidBodyType::Dynamic && rb.is_enabled() && !rb.is_frozen()
+        }) == Some(true)
+        {
             let rb = bodies.index_mut_internal(handle);
             rb.activation.wake_up(strong);
 
@@ -115,11 +124,47 @@ impl IslandManager {
         &self.active_dynamic_set[..]
     }
 
+    /// Is the given rigid-body part of the active dynamic or kinematic set?
+    pub(crate) fn is_in_active_set(&self, handle: RigidBodyHandle, ids: &RigidBodyIds) -> bool {
+        self.active_dynamic_set.get(ids.active_set_id) == Some(&handle)
+            || self.active_kinematic_set.get(ids.active_set_id) == Some(&handle)
+    }
+
     pub(crate) fn active_island(&self, island_id: usize) -> &[RigidBodyHandle] {
         let island_range = self.active_islands[island_id]..self.active_islands[island_id + 1];
         &self.active_dynamic_set[island_range]
     }
 
+    /// The rigid-bodies part of the given active island, as computed during the last timestep.
+    ///
+    /// Returns an empty slice if `island_id` is not smaller than `self.num_islands()`.
+    pub fn island_bodies(&self, island_id: usize) -> &[RigidBodyHandle] {
+        if island_id < self.num_islands() {
+            self.active_island(island_id)
+        } else {
+            &[]
+        }
+    }
+
+    /// The active island the given rigid-body belonged to during the last timestep.
+    ///
+    /// Returns `None` if the rigid-body doesn't exist, isn't dynamic, is sleeping, or is frozen.
+    pub fn island_of(&self, bodies: &RigidBodySet, handle: RigidBodyHandle) -> Option<usize> {
+        let rb = bodies.get(handle)?;
+        let island_id = rb.ids.active_island_id;
+
+        if rb.is_dynamic()
+            && !rb.is_sleeping()
+            && !rb.is_frozen()
+            && island_id < self.num_islands()
+            && self.active_island(island_id).get(rb.ids.active_set_offset) == Some(&handle)
+        {
+            Some(island_id)
+        } else {
+            None
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn iter_active_bodies<'a>(&'a self) -> impl Iterator<Item = RigidBodyHandle> + 'a {
         self.active_dynamic_set
@@ -142,6 +187,7 @@ impl IslandManager {
         impulse_joints: &ImpulseJointSet,
         multibody_joints: &MultibodyJointSet,
         min_island_size: usize,
+        events: &dyn EventHandler,
     ) {
         assert!(
             min_island_size > 0,
@@ -164,6 +210,12 @@ impl IslandManager {
             let stack = &mut self.stack;
 
             let rb = bodies.index_mut_internal(h);
+
+            if rb.is_frozen() {
+                // Frozen bodies leave the active set without being put to sleep.
+                continue;
+            }
+
             let sq_linvel = rb.vels.linvel.norm_squared();
             let sq_angvel = rb.vels.angvel.gdot(rb.vels.angvel);
 
@@ -235,9 +287,13 @@ impl IslandManager {
         while let Some(handle) = self.stack.pop() {
             let rb = bodies.index_mut_internal(handle);
 
-            if rb.ids.active_set_timestamp == self.active_set_timestamp || !rb.is_dynamic() {
+            if rb.ids.active_set_timestamp == self.active_set_timestamp
+                || !rb.is_dynamic()
+                || !rb.enabled
+                || rb.is_frozen()
+            {
                 // We already visited this body and its neighbors.
-                // Also, we don't propagate awake state through fixed bodies.
+                // Also, we don't propagate awake state through fixed, disabled, or frozen bodies.
                 continue;
             }
 
@@ -265,6 +321,11 @@ impl IslandManager {
                 self.stack.push(other);
             }
 
+            if rb.ids.sleep_reported {
+                rb.ids.sleep_reported = false;
+                events.handle_sleep_event(handle, false);
+            }
+
             rb.activation.wake_up(false);
             rb.ids.active_island_id = self.active_islands.len() - 1;
             rb.ids.active_set_id = self.active_dynamic_set.len();
@@ -279,7 +340,7 @@ impl IslandManager {
         //        println!(
         //            "Extraction: {}, num islands: {}",
         //            instant::now() - t,
-        //            self.active_islands.len() - 1
+        //            self.active_islands.len().saturating_sub(1)
         //        );
 
         // Actually put to sleep bodies which have not been detected as awake.
@@ -288,6 +349,11 @@ impl IslandManager {
             if rb.activation.sleeping {
                 rb.vels = RigidBodyVelocity::zero();
                 rb.activation.sleep();
+
+                if !rb.ids.sleep_reported {
+                    rb.ids.sleep_reported = true;
+                    events.handle_sleep_event(*handle, true);
+                }
             }
         }
     }
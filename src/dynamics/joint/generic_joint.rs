@@ -119,6 +119,13 @@ pub struct JointLimits<N> {
     pub max: N,
     /// The impulse applied to enforce the joint’s limit.
     pub impulse: N,
+    /// The restitution coefficient applied when the joint hits this limit.
+    ///
+    /// A value of 0 (the default) stops the motion along the limited axis, while a value
+    /// of 1 reflects all the velocity along that axis. No restitution is applied if the relative
+    /// velocity along that axis is smaller than `IntegrationParameters::restitution_velocity_threshold`.
+    /// This is ignored for the limits of coupled axes.
+    pub restitution: N,
 }
 
 impl<N: WReal> Default for JointLimits<N> {
@@ -127,6 +134,7 @@ impl<N: WReal> Default for JointLimits<N> {
             min: -N::splat(Real::MAX),
             max: N::splat(Real::MAX),
             impulse: N::splat(0.0),
+            restitution: N::splat(0.0),
         }
     }
 }
@@ -343,6 +351,15 @@ impl GenericJoint {
         self
     }
 
+    /// Sets the restitution coefficient applied when this joint hits its limits along the specified axis.
+    ///
+    /// This has no effect unless limits are also set along this axis with [`Self::set_limits`].
+    /// This is ignored if this axis is part of the coupled axes of this joint.
+    pub fn set_limit_restitution(&mut self, axis: JointAxis, restitution: Real) -> &mut Self {
+        self.limits[axis as usize].restitution = restitution;
+        self
+    }
+
     /// The spring-like motor model along the specified axis of this joint.
     #[must_use]
     pub fn motor_model(&self, axis: JointAxis) -> Option<MotorModel> {
@@ -547,6 +564,13 @@ impl GenericJointBuilder {
         self
     }
 
+    /// Sets the restitution coefficient applied when the joint hits its limits along the specified axis.
+    #[must_use]
+    pub fn limit_restitution(mut self, axis: JointAxis, restitution: Real) -> Self {
+        self.0.set_limit_restitution(axis, restitution);
+        self
+    }
+
     /// Sets the coupled degrees of freedom for this joint’s limits and motor.
     #[must_use]
     pub fn coupled_axes(mut self, axes: JointAxesMask) -> Self {
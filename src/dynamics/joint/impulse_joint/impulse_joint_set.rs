@@ -90,6 +90,19 @@ impl ImpulseJointSet {
             .map(|inter| (inter.0, inter.1, inter.2.handle, inter.2))
     }
 
+    /// Iterates through the handles of all the impulse joints attached to both given rigid-bodies.
+    ///
+    /// Two rigid-bodies can be linked by several joints, so this may yield more than one handle.
+    pub fn joints_between<'a>(
+        &'a self,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+    ) -> impl Iterator<Item = ImpulseJointHandle> + 'a {
+        self.attached_joints(body1)
+            .filter(move |(b1, b2, _, _)| crate::utils::select_other((*b1, *b2), body1) == body2)
+            .map(|(_, _, handle, _)| handle)
+    }
+
     /// Is the given joint handle valid?
     pub fn contains(&self, handle: ImpulseJointHandle) -> bool {
         self.joint_ids.contains(handle.0)
@@ -171,7 +184,6 @@ impl ImpulseJointSet {
     //     //     .map(|e| &mut e.weight)
     // }
 
-    #[cfg(not(feature = "parallel"))]
     pub(crate) fn joints_mut(&mut self) -> &mut [JointGraphEdge] {
         &mut self.joint_graph.graph.edges[..]
     }
@@ -249,12 +261,17 @@ impl ImpulseJointSet {
             let joint = &edge.weight;
             let rb1 = &bodies[joint.body1];
             let rb2 = &bodies[joint.body2];
-
-            if (rb1.is_dynamic() || rb2.is_dynamic())
-                && (!rb1.is_dynamic() || !rb1.is_sleeping())
-                && (!rb2.is_dynamic() || !rb2.is_sleeping())
+            // Frozen bodies are seen as fixed bodies.
+            let is_dynamic1 = rb1.effective_body_type().is_dynamic();
+            let is_dynamic2 = rb2.effective_body_type().is_dynamic();
+
+            if (is_dynamic1 || is_dynamic2)
+                && rb1.enabled
+                && rb2.enabled
+                && (!is_dynamic1 || !rb1.is_sleeping())
+                && (!is_dynamic2 || !rb2.is_sleeping())
             {
-                let island_index = if !rb1.is_dynamic() {
+                let island_index = if !is_dynamic1 {
                     rb2.ids.active_island_id
                 } else {
                     rb1.ids.active_island_id
@@ -5,6 +5,7 @@ pub use self::motor_model::MotorModel;
 pub use self::multibody_joint::*;
 pub use self::prismatic_joint::*;
 pub use self::revolute_joint::*;
+pub use self::spring_joint::*;
 
 #[cfg(feature = "dim3")]
 pub use self::spherical_joint::*;
@@ -16,6 +17,7 @@ mod motor_model;
 mod multibody_joint;
 mod prismatic_joint;
 mod revolute_joint;
+mod spring_joint;
 
 #[cfg(feature = "dim3")]
 mod spherical_joint;
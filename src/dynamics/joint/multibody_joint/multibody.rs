@@ -963,7 +963,7 @@ impl Multibody {
         out: &mut Vec<AnyJointVelocityConstraint>,
         mut insert_at: Option<usize>,
     ) {
-        if !cfg!(feature = "parallel") {
+        if insert_at.is_none() {
             let num_constraints: usize = self
                 .links
                 .iter()
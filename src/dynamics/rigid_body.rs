@@ -4,7 +4,8 @@ use crate::dynamics::{
     RigidBodyMassProps, RigidBodyPosition, RigidBodyType, RigidBodyVelocity,
 };
 use crate::geometry::{
-    Collider, ColliderHandle, ColliderMassProps, ColliderParent, ColliderPosition, ColliderShape,
+    Collider, ColliderHandle, ColliderMassProps, ColliderParent, ColliderPosition, ColliderSet,
+    ColliderShape, InteractionGroups, AABB,
 };
 use crate::math::{AngVector, Isometry, Point, Real, Rotation, Vector};
 use crate::utils::{self, WCross};
@@ -36,6 +37,12 @@ pub struct RigidBody {
     pub(crate) body_type: RigidBodyType,
     /// The dominance group this rigid-body is part of.
     pub(crate) dominance: RigidBodyDominance,
+    /// The solver groups this rigid-body is part of.
+    pub(crate) solver_groups: InteractionGroups,
+    /// Whether or not this rigid-body takes part in the simulation.
+    pub(crate) enabled: bool,
+    /// The velocities of this rigid-body before it was frozen, if it is frozen.
+    pub(crate) frozen_vels: Option<RigidBodyVelocity>,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
 }
@@ -62,6 +69,9 @@ impl RigidBody {
             changes: RigidBodyChanges::all(),
             body_type: RigidBodyType::Dynamic,
             dominance: RigidBodyDominance::default(),
+            solver_groups: InteractionGroups::all(),
+            enabled: true,
+            frozen_vels: None,
             user_data: 0,
         }
     }
@@ -89,6 +99,10 @@ impl RigidBody {
     }
 
     /// Sets the linear damping coefficient of this rigid-body.
+    ///
+    /// The linear velocity is multiplied by `1 / (1 + damping * dt)` at each (sub)step of length
+    /// `dt`. This approximates `exp(-damping * dt)`, so the damping applied over a timestep
+    /// barely depends on the number of CCD or solver substeps. The default is zero (no damping).
     #[inline]
     pub fn set_linear_damping(&mut self, damping: Real) {
         self.damping.linear_damping = damping;
@@ -101,6 +115,8 @@ impl RigidBody {
     }
 
     /// Sets the angular damping coefficient of this rigid-body.
+    ///
+    /// This is applied like the linear damping (see `Self::set_linear_damping`).
     #[inline]
     pub fn set_angular_damping(&mut self, damping: Real) {
         self.damping.angular_damping = damping
@@ -111,6 +127,19 @@ impl RigidBody {
         self.body_type
     }
 
+    /// The type of this rigid-body, as seen by the constraints solver.
+    ///
+    /// This is the same as `self.body_type()`, except for frozen dynamic rigid-bodies which
+    /// are seen as fixed rigid-bodies.
+    #[inline]
+    pub(crate) fn effective_body_type(&self) -> RigidBodyType {
+        if self.is_frozen() && self.body_type.is_dynamic() {
+            RigidBodyType::Fixed
+        } else {
+            self.body_type
+        }
+    }
+
     /// Sets the type of this rigid-body.
     pub fn set_body_type(&mut self, status: RigidBodyType) {
         if status != self.body_type {
@@ -132,10 +161,10 @@ impl RigidBody {
     /// The dominance group of this rigid-body.
     ///
     /// This method always returns `i8::MAX + 1` for non-dynamic
-    /// rigid-bodies.
+    /// and frozen rigid-bodies.
     #[inline]
     pub fn effective_dominance_group(&self) -> i16 {
-        self.dominance.effective_group(&self.body_type)
+        self.dominance.effective_group(&self.effective_body_type())
     }
 
     /// Sets the axes along which this rigid-body cannot translate or rotate.
@@ -290,6 +319,14 @@ impl RigidBody {
         self.ccd.ccd_enabled = enabled;
     }
 
+    /// Enables of disable CCD (continuous collision-detection) for this rigid-body.
+    ///
+    /// This is the same as `self.enable_ccd`. Only the rigid-bodies with CCD enabled are
+    /// considered by the CCD solver, so CCD can be restricted to fast-moving bodies like bullets.
+    pub fn set_ccd_enabled(&mut self, enabled: bool) {
+        self.enable_ccd(enabled)
+    }
+
     /// Is CCD (continous collision-detection) enabled for this rigid-body?
     pub fn is_ccd_enabled(&self) -> bool {
         self.ccd.ccd_enabled
@@ -332,6 +369,19 @@ impl RigidBody {
         self.update_world_mass_properties();
     }
 
+    /// Recomputes the mass properties of this rigid-body from its attached colliders.
+    ///
+    /// This is done automatically at the beginning of the next timestep whenever an attached
+    /// collider is modified. Call this to have the new mass properties reflect changes made,
+    /// e.g., to the shapes of the attached colliders, right away.
+    pub fn recompute_mass_properties_from_colliders(&mut self, colliders: &ColliderSet) {
+        self.mprops.recompute_mass_properties_from_colliders(
+            colliders,
+            &self.colliders,
+            &self.pos.position,
+        );
+    }
+
     /// The handles of colliders attached to this rigid body.
     pub fn colliders(&self) -> &[ColliderHandle] {
         &self.colliders.0[..]
@@ -404,6 +454,73 @@ impl RigidBody {
         }
     }
 
+    /// The solver groups of this rigid-body.
+    pub fn solver_groups(&self) -> InteractionGroups {
+        self.solver_groups
+    }
+
+    /// Sets the solver groups of this rigid-body.
+    ///
+    /// Contacts between two rigid-bodies with incompatible solver groups are still computed, and
+    /// still generate collision events, but they are ignored by the constraints solver. This is
+    /// unlike collision groups which prevent the computation of these contacts altogether.
+    /// These solver groups are combined with the solver groups of the colliders: forces are only
+    /// computed if both the rigid-bodies and the colliders solver groups are compatible.
+    /// Joints are not affected by these groups.
+    pub fn set_solver_groups(&mut self, groups: InteractionGroups) {
+        self.solver_groups = groups;
+    }
+
+    /// Is this rigid-body enabled?
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables this rigid-body.
+    ///
+    /// A disabled rigid-body is removed from the simulation without being removed from its
+    /// `RigidBodySet`: it is not integrated, it can't be woken up, its colliders don't generate
+    /// any contact or intersection, and the joints attached to it are ignored by the constraints
+    /// solver. Its handle, position, velocities, colliders, and joints are preserved so that
+    /// it resumes exactly where it stopped once it is enabled again.
+    ///
+    /// Note that the colliders of a disabled rigid-body are still taken into account by the
+    /// scene queries of the `QueryPipeline`.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled != enabled {
+            self.enabled = enabled;
+            self.changes.insert(RigidBodyChanges::ENABLED_OR_DISABLED);
+        }
+    }
+
+    /// Is this rigid-body frozen?
+    ///
+    /// See [`RigidBodySet::freeze_island`](crate::dynamics::RigidBodySet::freeze_island).
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_vels.is_some()
+    }
+
+    /// Freezes this rigid-body if it is dynamic and not already frozen.
+    ///
+    /// Its type is left unchanged, but it behaves like a fixed rigid-body until it is unfrozen.
+    /// Its velocities are saved so they can be restored afterwards.
+    pub(crate) fn freeze(&mut self) {
+        if self.is_dynamic() && !self.is_frozen() {
+            self.frozen_vels = Some(self.vels);
+            self.vels = RigidBodyVelocity::zero();
+            self.changes.insert(RigidBodyChanges::FROZEN);
+        }
+    }
+
+    /// Resumes the simulation of this rigid-body, with the velocities it had before being
+    /// frozen. Does nothing if it isn't frozen.
+    pub(crate) fn unfreeze(&mut self) {
+        if let Some(vels) = self.frozen_vels.take() {
+            self.vels = vels;
+            self.changes.insert(RigidBodyChanges::FROZEN);
+        }
+    }
+
     /// Adds a collider to this rigid-body.
     // TODO ECS: we keep this public for now just to simply our experiments on bevy_rapier.
     pub fn add_collider(
@@ -464,6 +581,10 @@ impl RigidBody {
     }
 
     /// Is this rigid body sleeping?
+    ///
+    /// This is updated by the active-set computation at the end of each `PhysicsPipeline::step`,
+    /// so it is accurate right after a step: a sleeping body isn't part of the active set, and
+    /// won't move until it is woken up.
     pub fn is_sleeping(&self) -> bool {
         // TODO: should we:
         // - return false for fixed bodies.
@@ -565,6 +686,23 @@ impl RigidBody {
         &self.pos.position
     }
 
+    /// The position of this rigid-body interpolated between the beginning and the end of the last timestep.
+    ///
+    /// With `alpha = 0.0` this returns the position at the beginning of the last timestep, and with
+    /// `alpha = 1.0` this returns the current position. This is typically used with the
+    /// `PhysicsAccumulator::alpha` for rendering smoothly a simulation running with a fixed timestep.
+    /// Sleeping rigid-bodies don’t move so their current position is returned.
+    #[must_use]
+    pub fn predict_position(&self, alpha: Real) -> Isometry<Real> {
+        if self.activation.sleeping {
+            self.pos.position
+        } else {
+            self.pos
+                .previous_position
+                .lerp_slerp(&self.pos.position, alpha)
+        }
+    }
+
     /// The translational part of this rigid-body's position.
     #[inline]
     pub fn translation(&self) -> &Vector<Real> {
@@ -580,6 +718,7 @@ impl RigidBody {
             self.changes.insert(RigidBodyChanges::POSITION);
             self.pos.position.translation.vector = translation;
             self.pos.next_position.translation.vector = translation;
+            self.pos.previous_position.translation.vector = translation;
 
             // TODO: Do we really need to check that the body isn't dynamic?
             if wake_up && self.is_dynamic() {
@@ -603,6 +742,7 @@ impl RigidBody {
             self.changes.insert(RigidBodyChanges::POSITION);
             self.pos.position.rotation = rotation;
             self.pos.next_position.rotation = rotation;
+            self.pos.previous_position.rotation = rotation;
 
             // TODO: Do we really need to check that the body isn't dynamic?
             if wake_up && self.is_dynamic() {
@@ -625,6 +765,7 @@ impl RigidBody {
             self.changes.insert(RigidBodyChanges::POSITION);
             self.pos.position = pos;
             self.pos.next_position = pos;
+            self.pos.previous_position = pos;
 
             // TODO: Do we really need to check that the body isn't dynamic?
             if wake_up && self.is_dynamic() {
@@ -654,6 +795,19 @@ impl RigidBody {
         }
     }
 
+    /// Cancels the motion clamping applied by the CCD to this rigid-body during the current substep.
+    ///
+    /// The next position of this rigid-body is reset to the one obtained by integrating its
+    /// velocity for a time of `dt`, ignoring the predicted impact. This is only meaningful
+    /// when called from `PhysicsHooks::on_ccd_motion_clamped`.
+    pub fn cancel_ccd_motion_clamping(&mut self, dt: Real) {
+        self.pos.next_position = self.integrated_vels.integrate(
+            dt,
+            &self.pos.position,
+            &self.mprops.local_mprops.local_com,
+        );
+    }
+
     /// Predicts the next position of this rigid-body, by integrating its velocity and forces
     /// by a time of `dt`.
     pub fn predict_position_using_velocity_and_forces(&self, dt: Real) -> Isometry<Real> {
@@ -668,6 +822,21 @@ impl RigidBody {
 
 /// ## Applying forces and torques
 impl RigidBody {
+    /// The sum of the constant forces manually applied to this rigid-body.
+    ///
+    /// These forces are kept from one timestep to the next, until reset with [`Self::reset_forces`].
+    /// This doesn't include gravity.
+    pub fn user_force(&self) -> Vector<Real> {
+        self.forces.user_force
+    }
+
+    /// The sum of the constant torques manually applied to this rigid-body.
+    ///
+    /// These torques are kept from one timestep to the next, until reset with [`Self::reset_torques`].
+    pub fn user_torque(&self) -> AngVector<Real> {
+        self.forces.user_torque
+    }
+
     /// Resets to zero all the constant (linear) forces manually applied to this rigid-body.
     pub fn reset_forces(&mut self, wake_up: bool) {
         if !self.forces.user_force.is_zero() {
@@ -690,7 +859,11 @@ impl RigidBody {
         }
     }
 
-    /// Adds to this rigid-body a constant force applied at its center-of-mass.ç
+    /// Adds to this rigid-body a constant force applied at its center-of-mass.
+    ///
+    /// This force is kept until it is reset with [`Self::reset_forces`]. If a timestep is split
+    /// into multiple CCD substeps, the force is integrated with the length of each substep so the
+    /// total impulse applied during one timestep remains `force * dt`.
     ///
     /// This does nothing on non-dynamic bodies.
     pub fn add_force(&mut self, force: Vector<Real>, wake_up: bool) {
@@ -839,6 +1012,25 @@ impl RigidBody {
 
         -self.mass() * self.forces.gravity_scale * gravity.dot(&world_com)
     }
+
+    /// Computes the world-space AABB enclosing all the colliders attached to this rigid-body.
+    ///
+    /// The AABBs are computed at the current position of this rigid-body. If no collider is
+    /// attached to this rigid-body, an invalid AABB (with `mins` greater than `maxs`) is returned.
+    pub fn compute_aabb(&self, colliders: &ColliderSet) -> AABB {
+        let mut aabb = AABB::new_invalid();
+
+        for handle in &self.colliders.0 {
+            let co = &colliders[*handle];
+            let co_pos = co
+                .parent
+                .map(|p| self.pos.position * p.pos_wrt_parent)
+                .unwrap_or(co.pos.0);
+            aabb.merge(&co.shape.compute_aabb(&co_pos));
+        }
+
+        aabb
+    }
 }
 
 /// A builder for rigid-bodies.
@@ -871,6 +1063,10 @@ pub struct RigidBodyBuilder {
     pub ccd_enabled: bool,
     /// The dominance group of the rigid-body to be built.
     pub dominance_group: i8,
+    /// The solver groups of the rigid-body to be built.
+    pub solver_groups: InteractionGroups,
+    /// Whether or not the rigid-body to be built is enabled.
+    pub enabled: bool,
     /// An arbitrary user-defined 128-bit integer associated to the rigid-bodies built by this builder.
     pub user_data: u128,
 }
@@ -892,6 +1088,8 @@ impl RigidBodyBuilder {
             sleeping: false,
             ccd_enabled: false,
             dominance_group: 0,
+            solver_groups: InteractionGroups::all(),
+            enabled: true,
             user_data: 0,
         }
     }
@@ -1153,6 +1351,23 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets the solver groups of this rigid-body.
+    ///
+    /// Forces between two rigid-bodies in contact will be computed iff their solver groups are
+    /// compatible. See [`RigidBody::set_solver_groups`] for details.
+    pub fn solver_groups(mut self, groups: InteractionGroups) -> Self {
+        self.solver_groups = groups;
+        self
+    }
+
+    /// Sets whether or not the rigid-body to be built is enabled.
+    ///
+    /// See [`RigidBody::set_enabled`] for details.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
     /// Sets whether or not the rigid-body is to be created asleep.
     pub fn sleeping(mut self, sleeping: bool) -> Self {
         self.sleeping = sleeping;
@@ -1179,6 +1394,8 @@ impl RigidBodyBuilder {
         rb.damping.angular_damping = self.angular_damping;
         rb.forces.gravity_scale = self.gravity_scale;
         rb.dominance = RigidBodyDominance(self.dominance_group);
+        rb.solver_groups = self.solver_groups;
+        rb.enabled = self.enabled;
         rb.enable_ccd(self.ccd_enabled);
 
         if self.can_sleep && self.sleeping {
@@ -22,6 +22,15 @@ impl RigidBodyHandle {
         self.0.into_raw_parts()
     }
 
+    /// The index of the slot this handle points to, without its generation.
+    ///
+    /// This index remains valid as long as the object stays in the `RigidBodySet`, and can be used to
+    /// store per-object data into a flat array. It may be reused by another object once this one
+    /// is removed. Use `RigidBodySet::get_unknown_gen` to retrieve the object from this index.
+    pub fn index(self) -> u32 {
+        self.0.into_raw_parts().0
+    }
+
     /// Reconstructs an handle from its (index, generation) components.
     pub fn from_raw_parts(id: u32, generation: u32) -> Self {
         Self(crate::data::arena::Index::from_raw_parts(id, generation))
@@ -110,6 +119,10 @@ bitflags::bitflags! {
         const TYPE        = 1 << 4;
         /// Flag indicating that the `RigidBodyDominance` component of this rigid-body has been modified.
         const DOMINANCE   = 1 << 5;
+        /// Flag indicating that this rigid-body has been enabled or disabled.
+        const ENABLED_OR_DISABLED = 1 << 6;
+        /// Flag indicating that this rigid-body has been frozen or unfrozen.
+        const FROZEN = 1 << 7;
     }
 }
 
@@ -135,6 +148,11 @@ pub struct RigidBodyPosition {
     /// resolution. Then it is either validated (ie. we set position := set_position)
     /// or clamped by CCD.
     pub next_position: Isometry<Real>,
+    /// The position of the rigid-body at the beginning of the last timestep.
+    ///
+    /// This is used for interpolating the rendered position of the rigid-body
+    /// between two timesteps. Teleporting the rigid-body resets it to the new position.
+    pub previous_position: Isometry<Real>,
 }
 
 impl Default for RigidBodyPosition {
@@ -142,6 +160,7 @@ impl Default for RigidBodyPosition {
         Self {
             position: Isometry::identity(),
             next_position: Isometry::identity(),
+            previous_position: Isometry::identity(),
         }
     }
 }
@@ -194,6 +213,7 @@ where
         Self {
             position,
             next_position: position,
+            previous_position: position,
         }
     }
 }
@@ -511,6 +531,19 @@ impl RigidBodyVelocity {
         }
     }
 
+    /// Returns the velocities with all the components smaller than `threshold` in absolute value set to zero.
+    #[must_use]
+    pub fn snap_to_zero(&self, threshold: Real) -> Self {
+        let snap = |x: Real| if x.abs() < threshold { 0.0 } else { x };
+        RigidBodyVelocity {
+            linvel: self.linvel.map(snap),
+            #[cfg(feature = "dim2")]
+            angvel: snap(self.angvel),
+            #[cfg(feature = "dim3")]
+            angvel: self.angvel.map(snap),
+        }
+    }
+
     /// The velocity of the given world-space point on this rigid-body.
     #[must_use]
     pub fn velocity_at_point(&self, point: &Point<Real>, world_com: &Point<Real>) -> Vector<Real> {
@@ -535,6 +568,16 @@ impl RigidBodyVelocity {
         result
     }
 
+    /// Are all the components of these velocities finite (i.e. neither NaN nor infinite)?
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        #[cfg(feature = "dim2")]
+        let angvel_is_finite = self.angvel.is_finite();
+        #[cfg(feature = "dim3")]
+        let angvel_is_finite = self.angvel.iter().all(|x| x.is_finite());
+        self.linvel.iter().all(|x| x.is_finite()) && angvel_is_finite
+    }
+
     /// Are these velocities exactly equal to zero?
     #[must_use]
     pub fn is_zero(&self) -> bool {
@@ -809,6 +852,7 @@ pub struct RigidBodyIds {
     pub(crate) active_set_id: usize,
     pub(crate) active_set_offset: usize,
     pub(crate) active_set_timestamp: u32,
+    pub(crate) sleep_reported: bool,
 }
 
 impl Default for RigidBodyIds {
@@ -818,6 +862,7 @@ impl Default for RigidBodyIds {
             active_set_id: 0,
             active_set_offset: 0,
             active_set_timestamp: 0,
+            sleep_reported: false,
         }
     }
 }
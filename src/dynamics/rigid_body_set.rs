@@ -1,8 +1,11 @@
 use crate::data::Arena;
 use crate::dynamics::{
-    ImpulseJointSet, IslandManager, MultibodyJointSet, RigidBody, RigidBodyChanges, RigidBodyHandle,
+    ImpulseJointSet, IslandManager, MultibodyJointSet, RigidBody, RigidBodyChanges,
+    RigidBodyHandle, RigidBodyVelocity,
 };
-use crate::geometry::ColliderSet;
+use crate::geometry::{ColliderSet, NarrowPhase};
+use crate::math::{Isometry, Real};
+use std::collections::{HashMap, HashSet};
 use std::ops::{Index, IndexMut};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -32,6 +35,7 @@ pub struct RigidBodySet {
     // Could we avoid this?
     pub(crate) bodies: Arena<RigidBody>,
     pub(crate) modified_bodies: Vec<RigidBodyHandle>,
+    pub(crate) moved_bodies: Vec<RigidBodyHandle>,
 }
 
 impl RigidBodySet {
@@ -40,6 +44,7 @@ impl RigidBodySet {
         RigidBodySet {
             bodies: Arena::new(),
             modified_bodies: Vec::new(),
+            moved_bodies: Vec::new(),
         }
     }
 
@@ -47,6 +52,15 @@ impl RigidBodySet {
         std::mem::replace(&mut self.modified_bodies, vec![])
     }
 
+    /// Iterates through, and removes, the handles of the rigid-bodies that moved during the last timestep.
+    ///
+    /// This only contains the bodies moved by the `PhysicsPipeline`, not the ones teleported
+    /// by the user. This set is overwritten by each timestep, and may contain
+    /// handles of rigid-bodies removed since then.
+    pub fn drain_modified(&mut self) -> std::vec::Drain<RigidBodyHandle> {
+        self.moved_bodies.drain(..)
+    }
+
     /// The number of rigid bodies on this set.
     pub fn len(&self) -> usize {
         self.bodies.len()
@@ -75,6 +89,55 @@ impl RigidBodySet {
         handle
     }
 
+    /// Insert several rigid-bodies into this set and retrieve their handles.
+    ///
+    /// The handles are allocated in the same order as if each rigid-body was inserted with
+    /// `insert`: slots freed by previous removals are reused first, so the resulting set is
+    /// identical to the one obtained by inserting the rigid-bodies one at a time.
+    pub fn insert_batch(
+        &mut self,
+        bodies: impl IntoIterator<Item = RigidBody>,
+    ) -> Vec<RigidBodyHandle> {
+        let bodies = bodies.into_iter();
+        let mut handles = Vec::with_capacity(bodies.size_hint().0);
+        self.modified_bodies.reserve(bodies.size_hint().0);
+
+        for rb in bodies {
+            handles.push(self.insert(rb));
+        }
+
+        handles
+    }
+
+    /// Moves all the rigid-bodies of `other` into this set.
+    ///
+    /// Returns the mapping from the handles the rigid-bodies had in `other` to their new handles
+    /// in `self`. The rigid-bodies are inserted in the order of their handles in `other`, reusing
+    /// the slots freed by previous removals first, so the resulting handles are deterministic.
+    ///
+    /// The colliders and joints attached to these rigid-bodies are not moved. Insert them into
+    /// the collider and joint sets of `self` using the returned mapping to find their new
+    /// rigid-bodies, e.g., with `ColliderSet::insert_with_parent`. The mass properties of each
+    /// rigid-body are updated as its colliders are attached again.
+    pub fn append(&mut self, mut other: RigidBodySet) -> HashMap<RigidBodyHandle, RigidBodyHandle> {
+        other
+            .bodies
+            .drain()
+            .map(|(index, mut rb)| {
+                // Remove the contributions of the colliders left behind, they will
+                // be added back when these colliders are attached again.
+                rb.mprops.local_mprops = rb
+                    .mprops
+                    .additional_local_mprops
+                    .as_deref()
+                    .copied()
+                    .unwrap_or_default();
+                rb.update_world_mass_properties();
+                (RigidBodyHandle(index), self.insert(rb))
+            })
+            .collect()
+    }
+
     /// Removes a rigid-body, and all its attached colliders and impulse_joints, from these sets.
     pub fn remove(
         &mut self,
@@ -152,6 +215,13 @@ impl RigidBodySet {
         self.bodies.get(handle.0)
     }
 
+    /// Is the rigid-body with the given handle sleeping?
+    ///
+    /// Returns `false` if there is no rigid-body with this handle. See `RigidBody::is_sleeping`.
+    pub fn is_sleeping(&self, handle: RigidBodyHandle) -> bool {
+        self.get(handle).map(|rb| rb.is_sleeping()).unwrap_or(false)
+    }
+
     pub(crate) fn mark_as_modified(
         handle: RigidBodyHandle,
         rb: &mut RigidBody,
@@ -206,6 +276,169 @@ impl RigidBodySet {
         })
     }
 
+    /// The sum of the kinetic energies of all the awake and enabled dynamic rigid-bodies on this set.
+    ///
+    /// This relies on the world-space mass properties computed during the last timestep.
+    /// Tracking this value over time is a convenient way to check if a scene is settling
+    /// down or if it keeps oscillating, e.g., when tuning sleep thresholds.
+    pub fn total_kinetic_energy(&self) -> Real {
+        self.bodies
+            .iter()
+            .filter(|(_, rb)| rb.is_dynamic() && rb.is_enabled() && !rb.is_sleeping())
+            .map(|(_, rb)| rb.kinetic_energy())
+            .sum()
+    }
+
+    /// Computes the groups of dynamic rigid-bodies connected by contacts or joints, regardless of their sleep state.
+    ///
+    /// Unlike the islands of the `IslandManager`, these groups include sleeping rigid-bodies. As for
+    /// the islands, the connectivity isn't propagated through non-dynamic or disabled rigid-bodies,
+    /// which aren't part of any group. Only the contacts that will be seen by the constraints solver
+    /// are taken into account. This is mostly useful for debugging.
+    pub fn connected_components(
+        &self,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+    ) -> Vec<Vec<RigidBodyHandle>> {
+        let can_propagate = |rb: &RigidBody| rb.is_dynamic() && rb.is_enabled();
+        let mut visited = HashSet::new();
+        let mut stack = vec![];
+        let mut components = vec![];
+
+        for (root, root_rb) in self.iter() {
+            if !can_propagate(root_rb) || !visited.insert(root) {
+                continue;
+            }
+
+            let mut component = vec![];
+            stack.push(root);
+
+            while let Some(handle) = stack.pop() {
+                component.push(handle);
+                let rb = &self[handle];
+                let mut neighbors = vec![];
+
+                for co_handle in rb.colliders() {
+                    for pair in narrow_phase.contacts_with(*co_handle) {
+                        if pair
+                            .manifolds
+                            .iter()
+                            .any(|m| !m.data.solver_contacts.is_empty())
+                        {
+                            let other = crate::utils::select_other(
+                                (pair.collider1, pair.collider2),
+                                *co_handle,
+                            );
+                            neighbors.extend(colliders.get(other).and_then(|co| co.parent()));
+                        }
+                    }
+                }
+
+                neighbors.extend(
+                    impulse_joints
+                        .attached_joints(handle)
+                        .map(|(b1, b2, _, _)| crate::utils::select_other((b1, b2), handle)),
+                );
+                neighbors.extend(multibody_joints.attached_bodies(handle));
+
+                for other in neighbors {
+                    if self.get(other).map_or(false, can_propagate) && visited.insert(other) {
+                        stack.push(other);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Enables or disables the rigid-body with the given handle.
+    ///
+    /// See [`RigidBody::set_enabled`] for details. This does nothing if the rigid-body does not exist.
+    pub fn set_enabled(&mut self, handle: RigidBodyHandle, enabled: bool) {
+        if let Some(rb) = self.bodies.get_mut(handle.0) {
+            Self::mark_as_modified(handle, rb, &mut self.modified_bodies);
+            rb.set_enabled(enabled);
+        }
+    }
+
+    /// Freezes all the rigid-bodies of the given active island.
+    ///
+    /// Frozen rigid-bodies keep their type, but are neither integrated nor moved by the
+    /// constraints solver. Their colliders are still collidable: the other rigid-bodies hitting
+    /// them react as if they hit fixed rigid-bodies, whatever their dominance groups. The contacts
+    /// of the frozen rigid-bodies are kept, so freezing and unfreezing don't emit any contact event.
+    /// Unlike sleeping rigid-bodies, frozen rigid-bodies are never woken up by contacts or joints.
+    /// Their velocities read as zero while they are frozen, and are restored once unfrozen.
+    ///
+    /// The active islands are recomputed at each timestep (and frozen rigid-bodies are no
+    /// longer part of any island), so this returns the handles of the frozen rigid-bodies.
+    /// Give them to `self.unfreeze_island` to resume their simulation.
+    pub fn freeze_island(
+        &mut self,
+        islands: &IslandManager,
+        island_id: usize,
+    ) -> Vec<RigidBodyHandle> {
+        let handles = islands.island_bodies(island_id).to_vec();
+
+        for handle in &handles {
+            if let Some(rb) = self.bodies.get_mut(handle.0) {
+                Self::mark_as_modified(*handle, rb, &mut self.modified_bodies);
+                rb.freeze();
+            }
+        }
+
+        handles
+    }
+
+    /// Unfreezes the given rigid-bodies, previously frozen by `self.freeze_island`.
+    ///
+    /// They are woken up and given back the velocities they had when they were frozen. The
+    /// rigid-bodies that don't exist or are not frozen are ignored.
+    pub fn unfreeze_island(&mut self, frozen_bodies: &[RigidBodyHandle]) {
+        for handle in frozen_bodies {
+            if let Some(rb) = self.bodies.get_mut(handle.0) {
+                if rb.is_frozen() {
+                    Self::mark_as_modified(*handle, rb, &mut self.modified_bodies);
+                    rb.unfreeze();
+                }
+            }
+        }
+    }
+
+    /// Teleports the given rigid-body to a new position, and optionally resets its velocities to zero.
+    ///
+    /// The positions of the colliders attached to this rigid-body are updated immediately so that
+    /// scene queries see the rigid-body at its new position before the next timestep. This does
+    /// nothing if the rigid-body does not exist.
+    pub fn teleport(
+        &mut self,
+        handle: RigidBodyHandle,
+        position: Isometry<Real>,
+        clear_velocity: bool,
+        colliders: &mut ColliderSet,
+    ) {
+        if let Some(rb) = self.bodies.get_mut(handle.0) {
+            Self::mark_as_modified(handle, rb, &mut self.modified_bodies);
+            rb.set_position(position, true);
+
+            if clear_velocity {
+                rb.vels = RigidBodyVelocity::zero();
+            }
+
+            for co_handle in rb.colliders() {
+                if let Some(collider) = colliders.get_mut(*co_handle) {
+                    let new_pos = position * collider.position_wrt_parent().unwrap();
+                    collider.set_position(new_pos);
+                }
+            }
+        }
+    }
+
     /// Update colliders positions after rigid-bodies moved.
     ///
     /// When a rigid-body moves, the positions of the colliders attached to it need to be updated.
@@ -50,18 +50,18 @@ pub(crate) fn categorize_joints(
 ) {
     for joint_i in joint_indices {
         let joint = &impulse_joints[*joint_i].weight;
-        let rb1 = &bodies[joint.body1.0];
-        let rb2 = &bodies[joint.body2.0];
+        let is_dynamic1 = bodies[joint.body1.0].effective_body_type().is_dynamic();
+        let is_dynamic2 = bodies[joint.body2.0].effective_body_type().is_dynamic();
 
         if multibody_joints.rigid_body_link(joint.body1).is_some()
             || multibody_joints.rigid_body_link(joint.body2).is_some()
         {
-            if !rb1.is_dynamic() || !rb2.is_dynamic() {
+            if !is_dynamic1 || !is_dynamic2 {
                 generic_ground_joints.push(*joint_i);
             } else {
                 generic_nonground_joints.push(*joint_i);
             }
-        } else if !rb1.is_dynamic() || !rb2.is_dynamic() {
+        } else if !is_dynamic1 || !is_dynamic2 {
             ground_joints.push(*joint_i);
         } else {
             nonground_joints.push(*joint_i);
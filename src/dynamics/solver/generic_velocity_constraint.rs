@@ -44,8 +44,8 @@ impl GenericVelocityConstraint {
         let rb1 = &bodies[handle1];
         let rb2 = &bodies[handle2];
 
-        let (vels1, mprops1, type1) = (&rb1.vels, &rb1.mprops, &rb1.body_type);
-        let (vels2, mprops2, type2) = (&rb2.vels, &rb2.mprops, &rb2.body_type);
+        let (vels1, mprops1, type1) = (&rb1.vels, &rb1.mprops, rb1.effective_body_type());
+        let (vels2, mprops2, type2) = (&rb2.vels, &rb2.mprops, rb2.effective_body_type());
         let ccd_thickness = rb1.ccd.ccd_thickness + rb2.ccd.ccd_thickness;
 
         let multibody1 = multibodies
@@ -83,7 +83,7 @@ impl GenericVelocityConstraint {
         let required_jacobian_len =
             *jacobian_id + manifold.data.solver_contacts.len() * multibodies_ndof * 2 * DIM;
 
-        if jacobians.nrows() < required_jacobian_len && !cfg!(feature = "parallel") {
+        if jacobians.nrows() < required_jacobian_len && insert_at.is_none() {
             jacobians.resize_vertically_mut(required_jacobian_len, 0.0);
         }
 
@@ -111,6 +111,7 @@ impl GenericVelocityConstraint {
                     na::zero()
                 },
                 cfm_factor,
+                block_solve: false,
                 limit: 0.0,
                 mj_lambda1,
                 mj_lambda2,
@@ -193,8 +194,10 @@ impl GenericVelocityConstraint {
                     let is_bouncy = manifold_point.is_bouncy() as u32 as Real;
                     let is_resting = 1.0 - is_bouncy;
 
-                    let mut rhs_wo_bias = (1.0 + is_bouncy * manifold_point.restitution)
-                        * (vel1 - vel2).dot(&force_dir1);
+                    let dvel = (vel1 - vel2).dot(&force_dir1);
+                    let restitution = manifold_point
+                        .effective_restitution(dvel, params.restitution_velocity_threshold);
+                    let mut rhs_wo_bias = (1.0 + is_bouncy * restitution) * dvel;
                     rhs_wo_bias += manifold_point.dist.max(0.0) * inv_dt;
                     rhs_wo_bias *= is_bouncy + is_resting;
                     let rhs_bias =
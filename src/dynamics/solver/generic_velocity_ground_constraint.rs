@@ -75,7 +75,7 @@ impl GenericVelocityGroundConstraint {
         let required_jacobian_len =
             *jacobian_id + manifold.data.solver_contacts.len() * multibodies_ndof * 2 * DIM;
 
-        if jacobians.nrows() < required_jacobian_len && !cfg!(feature = "parallel") {
+        if jacobians.nrows() < required_jacobian_len && insert_at.is_none() {
             jacobians.resize_vertically_mut(required_jacobian_len, 0.0);
         }
 
@@ -94,6 +94,7 @@ impl GenericVelocityGroundConstraint {
                 elements: [VelocityGroundConstraintElement::zero(); MAX_MANIFOLD_POINTS],
                 im2: mprops2.effective_inv_mass,
                 cfm_factor,
+                block_solve: false,
                 limit: 0.0,
                 mj_lambda2,
                 manifold_id,
@@ -134,7 +135,9 @@ impl GenericVelocityGroundConstraint {
                     let is_resting = 1.0 - is_bouncy;
 
                     let dvel = (vel1 - vel2).dot(&force_dir1);
-                    let mut rhs_wo_bias = (1.0 + is_bouncy * manifold_point.restitution) * dvel;
+                    let restitution = manifold_point
+                        .effective_restitution(dvel, params.restitution_velocity_threshold);
+                    let mut rhs_wo_bias = (1.0 + is_bouncy * restitution) * dvel;
                     rhs_wo_bias += manifold_point.dist.max(0.0) * inv_dt;
                     rhs_wo_bias *= is_bouncy + is_resting;
                     let rhs_bias =
@@ -89,8 +89,14 @@ impl ParallelInteractionGroups {
             .zip(self.interaction_colors.iter_mut())
         {
             let mut body_pair = interactions[*interaction_id].body_pair();
-            let is_fixed1 = body_pair.0.map(|b| bodies[b].is_fixed()).unwrap_or(true);
-            let is_fixed2 = body_pair.1.map(|b| bodies[b].is_fixed()).unwrap_or(true);
+            let is_fixed1 = body_pair
+                .0
+                .map(|b| bodies[b].effective_body_type().is_fixed())
+                .unwrap_or(true);
+            let is_fixed2 = body_pair
+                .1
+                .map(|b| bodies[b].effective_body_type().is_fixed())
+                .unwrap_or(true);
 
             let representative = |handle: RigidBodyHandle| {
                 if let Some(link) = multibodies.rigid_body_link(handle).copied() {
@@ -254,8 +260,8 @@ impl InteractionGroups {
             let rb1 = &bodies[interaction.body1];
             let rb2 = &bodies[interaction.body2];
 
-            let is_fixed1 = !rb1.is_dynamic();
-            let is_fixed2 = !rb2.is_dynamic();
+            let is_fixed1 = !rb1.effective_body_type().is_dynamic();
+            let is_fixed2 = !rb2.effective_body_type().is_dynamic();
 
             if is_fixed1 && is_fixed2 {
                 continue;
@@ -413,14 +419,14 @@ impl InteractionGroups {
                 let (status1, active_set_offset1) = if let Some(rb1) = interaction.data.rigid_body1
                 {
                     let rb1 = &bodies[rb1];
-                    (rb1.body_type, rb1.ids.active_set_offset)
+                    (rb1.effective_body_type(), rb1.ids.active_set_offset)
                 } else {
                     (RigidBodyType::Fixed, usize::MAX)
                 };
                 let (status2, active_set_offset2) = if let Some(rb2) = interaction.data.rigid_body2
                 {
                     let rb2 = &bodies[rb2];
-                    (rb2.body_type, rb2.ids.active_set_offset)
+                    (rb2.effective_body_type(), rb2.ids.active_set_offset)
                 } else {
                     (RigidBodyType::Fixed, usize::MAX)
                 };
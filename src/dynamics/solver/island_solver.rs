@@ -6,6 +6,7 @@ use crate::dynamics::solver::{
 use crate::dynamics::IslandManager;
 use crate::dynamics::{IntegrationParameters, JointGraphEdge, JointIndex, RigidBodySet};
 use crate::geometry::{ContactManifold, ContactManifoldIndex};
+use crate::math::Real;
 use crate::prelude::MultibodyJointSet;
 
 pub struct IslandSolver {
@@ -79,12 +80,62 @@ impl IslandSolver {
             bodies,
             multibody_joints,
             manifolds,
+            manifold_indices,
             impulse_joints,
             &mut self.contact_constraints.velocity_constraints,
             &self.contact_constraints.generic_jacobians,
             &mut self.joint_constraints.velocity_constraints,
             &self.joint_constraints.generic_jacobians,
+            counters.enabled(),
         );
         counters.solver.velocity_resolution_time.pause();
+
+        if counters.enabled() {
+            let position_residual =
+                compute_position_residual(params, bodies, manifolds, manifold_indices);
+            counters.solver.velocity_residual = counters
+                .solver
+                .velocity_residual
+                .max(self.velocity_solver.velocity_residual);
+            counters.solver.position_residual =
+                counters.solver.position_residual.max(position_residual);
+        }
     }
 }
+
+/// The largest penetration depth exceeding the allowed linear error, predicted from the
+/// contacts of the given manifolds and the velocities used to integrate the bodies positions.
+fn compute_position_residual(
+    params: &IntegrationParameters,
+    bodies: &RigidBodySet,
+    manifolds: &[&mut ContactManifold],
+    manifold_indices: &[ContactManifoldIndex],
+) -> Real {
+    let mut residual: Real = 0.0;
+
+    for manifold_id in manifold_indices {
+        let manifold = &manifolds[*manifold_id];
+        let rb1 = manifold.data.rigid_body1.and_then(|h| bodies.get(h));
+        let rb2 = manifold.data.rigid_body2.and_then(|h| bodies.get(h));
+
+        for contact in &manifold.data.solver_contacts {
+            let point_vel = |rb: Option<&crate::dynamics::RigidBody>| {
+                rb.map(|rb| {
+                    let vels = if rb.effective_body_type().is_dynamic() {
+                        &rb.integrated_vels
+                    } else {
+                        &rb.vels
+                    };
+                    vels.velocity_at_point(&contact.point, &rb.mprops.world_com)
+                })
+                .unwrap_or_else(na::zero)
+            };
+
+            let normal_vel = (point_vel(rb2) - point_vel(rb1)).dot(&manifold.data.normal);
+            let predicted_dist = contact.dist + normal_vel * params.dt;
+            residual = residual.max(-predicted_dist - params.allowed_linear_error);
+        }
+    }
+
+    residual
+}
@@ -107,7 +107,7 @@ impl AnyJointVelocityConstraint {
             // TODO: is this count correct when we take both motors and limits into account?
             let required_jacobian_len = *j_id + multibodies_ndof * 2 * SPATIAL_DIM;
 
-            if jacobians.nrows() < required_jacobian_len && !cfg!(feature = "parallel") {
+            if jacobians.nrows() < required_jacobian_len && insert_at.is_none() {
                 jacobians.resize_vertically_mut(required_jacobian_len, 0.0);
             }
 
@@ -266,7 +266,7 @@ impl AnyJointVelocityConstraint {
     ) {
         let mut handle1 = joint.body1;
         let mut handle2 = joint.body2;
-        let flipped = !bodies[handle2].is_dynamic();
+        let flipped = !bodies[handle2].effective_body_type().is_dynamic();
 
         let (local_frame1, local_frame2) = if flipped {
             std::mem::swap(&mut handle1, &mut handle2);
@@ -316,7 +316,7 @@ impl AnyJointVelocityConstraint {
             // TODO: is this count correct when we take both motors and limits into account?
             let required_jacobian_len = *j_id + multibodies_ndof * 2 * SPATIAL_DIM;
 
-            if jacobians.nrows() < required_jacobian_len && !cfg!(feature = "parallel") {
+            if jacobians.nrows() < required_jacobian_len && insert_at.is_none() {
                 jacobians.resize_vertically_mut(required_jacobian_len, 0.0);
             }
 
@@ -386,7 +386,8 @@ impl AnyJointVelocityConstraint {
 
         let mut handles1 = gather![|ii| impulse_joints[ii].body1];
         let mut handles2 = gather![|ii| impulse_joints[ii].body2];
-        let status2: [&RigidBodyType; SIMD_WIDTH] = gather![|ii| &bodies[handles2[ii]].body_type];
+        let status2: [RigidBodyType; SIMD_WIDTH] =
+            gather![|ii| bodies[handles2[ii]].effective_body_type()];
         let mut flipped = [false; SIMD_WIDTH];
 
         for ii in 0..SIMD_WIDTH {
@@ -179,6 +179,7 @@ impl JointGenericVelocityConstraint {
                     mb2,
                     i - DIM,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
@@ -197,6 +198,7 @@ impl JointGenericVelocityConstraint {
                     mb2,
                     i,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
@@ -469,6 +471,7 @@ impl JointGenericVelocityGroundConstraint {
                     mb2,
                     i - DIM,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
@@ -485,6 +488,7 @@ impl JointGenericVelocityGroundConstraint {
                     mb2,
                     i,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
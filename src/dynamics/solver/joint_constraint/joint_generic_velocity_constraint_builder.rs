@@ -2,7 +2,9 @@ use crate::dynamics::solver::joint_constraint::joint_generic_velocity_constraint
     JointGenericVelocityConstraint, JointGenericVelocityGroundConstraint,
 };
 use crate::dynamics::solver::joint_constraint::joint_velocity_constraint::WritebackId;
-use crate::dynamics::solver::joint_constraint::{JointVelocityConstraintBuilder, SolverBody};
+use crate::dynamics::solver::joint_constraint::{
+    effective_limit_restitution, JointVelocityConstraintBuilder, SolverBody,
+};
 use crate::dynamics::solver::MotorParameters;
 use crate::dynamics::{IntegrationParameters, JointIndex, Multibody};
 use crate::math::{Real, Vector, ANG_DIM, DIM, SPATIAL_DIM};
@@ -189,6 +191,7 @@ impl JointVelocityConstraintBuilder<Real> {
         mb2: Option<(&Multibody, usize)>,
         limited_axis: usize,
         limits: [Real; 2],
+        restitution: Real,
         writeback_id: WritebackId,
     ) -> JointGenericVelocityConstraint {
         let lin_jac = self.basis.column(limited_axis).into_owned();
@@ -216,7 +219,9 @@ impl JointVelocityConstraintBuilder<Real> {
 
         let erp_inv_dt = params.joint_erp_inv_dt();
         let rhs_bias = ((dist - limits[1]).max(0.0) - (limits[0] - dist).max(0.0)) * erp_inv_dt;
-        constraint.rhs += rhs_bias;
+        let restitution = effective_limit_restitution(params, restitution, constraint.rhs_wo_bias);
+        constraint.rhs_wo_bias *= 1.0 + restitution;
+        constraint.rhs = constraint.rhs_wo_bias + rhs_bias;
         constraint.impulse_bounds = [
             min_enabled as u32 as Real * -Real::MAX,
             max_enabled as u32 as Real * Real::MAX,
@@ -335,6 +340,7 @@ impl JointVelocityConstraintBuilder<Real> {
         mb2: Option<(&Multibody, usize)>,
         limited_axis: usize,
         limits: [Real; 2],
+        restitution: Real,
         writeback_id: WritebackId,
     ) -> JointGenericVelocityConstraint {
         let ang_jac = self.ang_basis.column(limited_axis).into_owned();
@@ -370,7 +376,9 @@ impl JointVelocityConstraintBuilder<Real> {
         let rhs_bias =
             ((s_ang - s_limits[1]).max(0.0) - (s_limits[0] - s_ang).max(0.0)) * erp_inv_dt;
 
-        constraint.rhs += rhs_bias;
+        let restitution = effective_limit_restitution(params, restitution, constraint.rhs_wo_bias);
+        constraint.rhs_wo_bias *= 1.0 + restitution;
+        constraint.rhs = constraint.rhs_wo_bias + rhs_bias;
         constraint.impulse_bounds = impulse_bounds;
         constraint
     }
@@ -583,6 +591,7 @@ impl JointVelocityConstraintBuilder<Real> {
         mb2: (&Multibody, usize),
         limited_axis: usize,
         limits: [Real; 2],
+        restitution: Real,
         writeback_id: WritebackId,
     ) -> JointGenericVelocityGroundConstraint {
         let lin_jac = self.basis.column(limited_axis).into_owned();
@@ -608,7 +617,9 @@ impl JointVelocityConstraintBuilder<Real> {
 
         let erp_inv_dt = params.joint_erp_inv_dt();
         let rhs_bias = ((dist - limits[1]).max(0.0) - (limits[0] - dist).max(0.0)) * erp_inv_dt;
-        constraint.rhs += rhs_bias;
+        let restitution = effective_limit_restitution(params, restitution, constraint.rhs_wo_bias);
+        constraint.rhs_wo_bias *= 1.0 + restitution;
+        constraint.rhs = constraint.rhs_wo_bias + rhs_bias;
         constraint.impulse_bounds = [
             min_enabled as u32 as Real * -Real::MAX,
             max_enabled as u32 as Real * Real::MAX,
@@ -718,6 +729,7 @@ impl JointVelocityConstraintBuilder<Real> {
         mb2: (&Multibody, usize),
         limited_axis: usize,
         limits: [Real; 2],
+        restitution: Real,
         writeback_id: WritebackId,
     ) -> JointGenericVelocityGroundConstraint {
         let ang_jac = self.ang_basis.column(limited_axis).into_owned();
@@ -751,7 +763,9 @@ impl JointVelocityConstraintBuilder<Real> {
         let rhs_bias =
             ((s_ang - s_limits[1]).max(0.0) - (s_limits[0] - s_ang).max(0.0)) * erp_inv_dt;
 
-        constraint.rhs += rhs_bias;
+        let restitution = effective_limit_restitution(params, restitution, constraint.rhs_wo_bias);
+        constraint.rhs_wo_bias *= 1.0 + restitution;
+        constraint.rhs = constraint.rhs_wo_bias + rhs_bias;
         constraint.impulse_bounds = impulse_bounds;
         constraint
     }
@@ -195,17 +195,30 @@ impl JointVelocityConstraint<Real, 1> {
         }
 
         if (motor_axes & coupled_axes) & JointAxesMask::LIN_AXES.bits() != 0 {
-            // TODO: coupled linear motor constraint.
-            // out[len] = builder.motor_linear_coupled(
-            //     params,
-            //     [joint_id],
-            //     body1,
-            //     body2,
-            //     limit_axes & coupled_axes,
-            //     &joint.limits,
-            //     WritebackId::Limit(0), // TODO: writeback
-            // );
-            // len += 1;
+            // The motor and limits of the coupled linear axes are the ones
+            // set on the first coupled linear axis.
+            let lin_coupled_axes = coupled_axes & JointAxesMask::LIN_AXES.bits();
+            let first_coupled_lin_axis_id = lin_coupled_axes.trailing_zeros() as usize;
+            let limits = if limit_axes & (1 << first_coupled_lin_axis_id) != 0 {
+                Some([
+                    joint.limits[first_coupled_lin_axis_id].min,
+                    joint.limits[first_coupled_lin_axis_id].max,
+                ])
+            } else {
+                None
+            };
+
+            out[len] = builder.motor_linear_coupled(
+                params,
+                [joint_id],
+                body1,
+                body2,
+                lin_coupled_axes,
+                &joint.motors[first_coupled_lin_axis_id].motor_params(params.dt),
+                limits,
+                WritebackId::Motor(first_coupled_lin_axis_id),
+            );
+            len += 1;
         }
 
         JointVelocityConstraintBuilder::finalize_constraints(&mut out[start..len]);
@@ -241,6 +254,7 @@ impl JointVelocityConstraint<Real, 1> {
                     body2,
                     i - DIM,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
@@ -255,6 +269,7 @@ impl JointVelocityConstraint<Real, 1> {
                     body2,
                     i,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
@@ -526,22 +541,30 @@ impl JointVelocityGroundConstraint<Real, 1> {
         }
 
         if (motor_axes & coupled_axes) & JointAxesMask::LIN_AXES.bits() != 0 {
-            /*
-            // TODO: coupled linear motor constraint.
+            // The motor and limits of the coupled linear axes are the ones
+            // set on the first coupled linear axis.
+            let lin_coupled_axes = coupled_axes & JointAxesMask::LIN_AXES.bits();
+            let first_coupled_lin_axis_id = lin_coupled_axes.trailing_zeros() as usize;
+            let limits = if limit_axes & (1 << first_coupled_lin_axis_id) != 0 {
+                Some([
+                    joint.limits[first_coupled_lin_axis_id].min,
+                    joint.limits[first_coupled_lin_axis_id].max,
+                ])
+            } else {
+                None
+            };
+
             out[len] = builder.motor_linear_coupled_ground(
                 params,
                 [joint_id],
                 body1,
                 body2,
-                motor_axes & coupled_axes,
-                &joint.motors,
-                limit_axes & coupled_axes,
-                &joint.limits,
-                WritebackId::Limit(0), // TODO: writeback
+                lin_coupled_axes,
+                &joint.motors[first_coupled_lin_axis_id].motor_params(params.dt),
+                limits,
+                WritebackId::Motor(first_coupled_lin_axis_id),
             );
             len += 1;
-            */
-            todo!()
         }
 
         JointVelocityConstraintBuilder::finalize_ground_constraints(&mut out[start..len]);
@@ -583,6 +606,7 @@ impl JointVelocityGroundConstraint<Real, 1> {
                     body2,
                     i - DIM,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
@@ -597,6 +621,7 @@ impl JointVelocityGroundConstraint<Real, 1> {
                     body2,
                     i,
                     [joint.limits[i].min, joint.limits[i].max],
+                    joint.limits[i].restitution,
                     WritebackId::Limit(i),
                 );
                 len += 1;
@@ -11,6 +11,21 @@ use na::SMatrix;
 #[cfg(feature = "dim3")]
 use crate::utils::WBasis;
 
+/// The restitution coefficient to apply to a joint limit, given the relative velocity `dvel`
+/// along the limited axis.
+///
+/// Like for contacts, this is zero if `dvel` is smaller than
+/// `IntegrationParameters::restitution_velocity_threshold` in absolute value, so a body resting
+/// against a limit doesn't bounce because of the velocity gained from gravity at each timestep.
+pub fn effective_limit_restitution<N: WReal>(
+    params: &IntegrationParameters,
+    restitution: N,
+    dvel: N,
+) -> N {
+    let threshold = N::splat(params.restitution_velocity_threshold);
+    restitution.select(dvel.simd_abs().simd_ge(threshold), N::zero())
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct JointVelocityConstraintBuilder<N: WReal> {
     pub basis: Matrix<N>,
@@ -87,6 +102,7 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         body2: &SolverBody<N, LANES>,
         limited_axis: usize,
         limits: [N; 2],
+        restitution: N,
         writeback_id: WritebackId,
     ) -> JointVelocityConstraint<N, LANES> {
         let zero = N::zero();
@@ -101,6 +117,8 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         let cfm_coeff = N::splat(params.joint_cfm_coeff());
         let rhs_bias =
             ((dist - limits[1]).simd_max(zero) - (limits[0] - dist).simd_max(zero)) * erp_inv_dt;
+        let restitution = effective_limit_restitution(params, restitution, constraint.rhs_wo_bias);
+        constraint.rhs_wo_bias = constraint.rhs_wo_bias * (N::one() + restitution);
         constraint.rhs = constraint.rhs_wo_bias + rhs_bias;
         constraint.cfm_coeff = cfm_coeff;
         constraint.impulse_bounds = [
@@ -225,6 +243,83 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         constraint
     }
 
+    pub fn motor_linear_coupled<const LANES: usize>(
+        &self,
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; LANES],
+        body1: &SolverBody<N, LANES>,
+        body2: &SolverBody<N, LANES>,
+        motor_coupled_axes: u8,
+        motor_params: &MotorParameters<N>,
+        limits: Option<[N; 2]>,
+        writeback_id: WritebackId,
+    ) -> JointVelocityConstraint<N, LANES> {
+        let inv_dt = N::splat(params.inv_dt());
+        let mut lin_jac = Vector::zeros();
+        let mut ang_jac1: AngVector<N> = na::zero();
+        let mut ang_jac2: AngVector<N> = na::zero();
+
+        for i in 0..DIM {
+            if motor_coupled_axes & (1 << i) != 0 {
+                let coeff = self.basis.column(i).dot(&self.lin_err);
+                lin_jac += self.basis.column(i) * coeff;
+                #[cfg(feature = "dim2")]
+                {
+                    ang_jac1 += self.cmat1_basis[i] * coeff;
+                    ang_jac2 += self.cmat2_basis[i] * coeff;
+                }
+                #[cfg(feature = "dim3")]
+                {
+                    ang_jac1 += self.cmat1_basis.column(i) * coeff;
+                    ang_jac2 += self.cmat2_basis.column(i) * coeff;
+                }
+            }
+        }
+
+        let dist = lin_jac.norm();
+        let inv_dist = crate::utils::simd_inv(dist);
+        lin_jac *= inv_dist;
+        ang_jac1 *= inv_dist;
+        ang_jac2 *= inv_dist;
+
+        let mut rhs_wo_bias = N::zero();
+        if motor_params.erp_inv_dt != N::zero() {
+            rhs_wo_bias += (dist - motor_params.target_pos) * motor_params.erp_inv_dt;
+        }
+
+        let mut target_vel = motor_params.target_vel;
+        if let Some(limits) = limits {
+            target_vel =
+                target_vel.simd_clamp((limits[0] - dist) * inv_dt, (limits[1] - dist) * inv_dt);
+        };
+
+        let dvel = lin_jac.dot(&(body2.linvel - body1.linvel))
+            + (ang_jac2.gdot(body2.angvel) - ang_jac1.gdot(body1.angvel));
+        rhs_wo_bias += dvel - target_vel;
+
+        ang_jac1 = body1.sqrt_ii * ang_jac1;
+        ang_jac2 = body2.sqrt_ii * ang_jac2;
+
+        JointVelocityConstraint {
+            joint_id,
+            mj_lambda1: body1.mj_lambda,
+            mj_lambda2: body2.mj_lambda,
+            im1: body1.im,
+            im2: body2.im,
+            impulse: N::zero(),
+            impulse_bounds: [-motor_params.max_impulse, motor_params.max_impulse],
+            lin_jac,
+            ang_jac1,
+            ang_jac2,
+            inv_lhs: N::zero(), // Will be set during ortogonalization.
+            cfm_coeff: motor_params.cfm_coeff,
+            cfm_gain: motor_params.cfm_gain,
+            rhs: rhs_wo_bias,
+            rhs_wo_bias,
+            writeback_id,
+        }
+    }
+
     pub fn lock_linear<const LANES: usize>(
         &self,
         params: &IntegrationParameters,
@@ -283,6 +378,7 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         body2: &SolverBody<N, LANES>,
         limited_axis: usize,
         limits: [N; 2],
+        restitution: N,
         writeback_id: WritebackId,
     ) -> JointVelocityConstraint<N, LANES> {
         let zero = N::zero();
@@ -305,7 +401,8 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         #[cfg(feature = "dim3")]
         let ang_jac = self.ang_basis.column(limited_axis).into_owned();
         let dvel = ang_jac.gdot(body2.angvel) - ang_jac.gdot(body1.angvel);
-        let rhs_wo_bias = dvel;
+        let restitution = effective_limit_restitution(params, restitution, dvel);
+        let rhs_wo_bias = dvel * (N::one() + restitution);
 
         let erp_inv_dt = N::splat(params.joint_erp_inv_dt());
         let cfm_coeff = N::splat(params.joint_cfm_coeff());
@@ -489,6 +586,7 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         body2: &SolverBody<N, LANES>,
         limited_axis: usize,
         limits: [N; 2],
+        restitution: N,
         writeback_id: WritebackId,
     ) -> JointVelocityGroundConstraint<N, LANES> {
         let zero = N::zero();
@@ -511,7 +609,8 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
 
         let dvel = lin_jac.dot(&(body2.linvel - body1.linvel))
             + (ang_jac2.gdot(body2.angvel) - ang_jac1.gdot(body1.angvel));
-        let rhs_wo_bias = dvel;
+        let restitution = effective_limit_restitution(params, restitution, dvel);
+        let rhs_wo_bias = dvel * (N::one() + restitution);
 
         let erp_inv_dt = N::splat(params.joint_erp_inv_dt());
         let cfm_coeff = N::splat(params.joint_cfm_coeff());
@@ -663,76 +762,78 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         }
     }
 
-    // pub fn motor_linear_coupled_ground<const LANES: usize>(
-    //     &self,
-    //     _joint_id: [JointIndex; LANES],
-    //     _body1: &SolverBody<N, LANES>,
-    //     _body2: &SolverBody<N, LANES>,
-    //     _motor_coupled_axes: u8,
-    //     _motors: &[MotorParameters<N>],
-    //     _limited_coupled_axes: u8,
-    //     _limits: &[JointLimits<N>],
-    //     _writeback_id: WritebackId,
-    // ) -> JointVelocityGroundConstraint<N, LANES> {
-    //     let zero = N::zero();
-    //     let mut lin_jac = Vector::zeros();
-    //     let mut ang_jac1: AngVector<N> = na::zero();
-    //     let mut ang_jac2: AngVector<N> = na::zero();
-    //     let mut limit = N::zero();
-
-    //     for i in 0..DIM {
-    //         if limited_coupled_axes & (1 << i) != 0 {
-    //             let coeff = self.basis.column(i).dot(&self.lin_err);
-    //             lin_jac += self.basis.column(i) * coeff;
-    //             #[cfg(feature = "dim2")]
-    //             {
-    //                 ang_jac1 += self.cmat1_basis[i] * coeff;
-    //                 ang_jac2 += self.cmat2_basis[i] * coeff;
-    //             }
-    //             #[cfg(feature = "dim3")]
-    //             {
-    //                 ang_jac1 += self.cmat1_basis.column(i) * coeff;
-    //                 ang_jac2 += self.cmat2_basis.column(i) * coeff;
-    //             }
-    //             limit += limits[i].max * limits[i].max;
-    //         }
-    //     }
-
-    //     limit = limit.simd_sqrt();
-    //     let dist = lin_jac.norm();
-    //     let inv_dist = crate::utils::simd_inv(dist);
-    //     lin_jac *= inv_dist;
-    //     ang_jac1 *= inv_dist;
-    //     ang_jac2 *= inv_dist;
-
-    //     let dvel = lin_jac.dot(&(body2.linvel - body1.linvel))
-    //         + (ang_jac2.gdot(body2.angvel) - ang_jac1.gdot(body1.angvel));
-    //     let rhs_wo_bias = dvel + (dist - limit).simd_min(zero) * N::splat(params.inv_dt());
-
-    //     ang_jac2 = body2.sqrt_ii * ang_jac2;
-
-    //     let erp_inv_dt = N::splat(params.joint_erp_inv_dt());
-    //     let cfm_coeff = N::splat(params.joint_cfm_coeff());
-    //     let rhs_bias = (dist - limit).simd_max(zero) * erp_inv_dt;
-    //     let rhs = rhs_wo_bias + rhs_bias;
-    //     let impulse_bounds = [N::zero(), N::splat(Real::INFINITY)];
-
-    //     JointVelocityGroundConstraint {
-    //         joint_id,
-    //         mj_lambda2: body2.mj_lambda,
-    //         im2: body2.im,
-    //         impulse: N::zero(),
-    //         impulse_bounds,
-    //         lin_jac,
-    //         ang_jac2,
-    //         inv_lhs: N::zero(), // Will be set during ortogonalization.
-    //         cfm_coeff,
-    //         cfm_gain: N::zero(),
-    //         rhs,
-    //         rhs_wo_bias,
-    //         writeback_id,
-    //     }
-    // }
+    pub fn motor_linear_coupled_ground<const LANES: usize>(
+        &self,
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; LANES],
+        body1: &SolverBody<N, LANES>,
+        body2: &SolverBody<N, LANES>,
+        motor_coupled_axes: u8,
+        motor_params: &MotorParameters<N>,
+        limits: Option<[N; 2]>,
+        writeback_id: WritebackId,
+    ) -> JointVelocityGroundConstraint<N, LANES> {
+        let inv_dt = N::splat(params.inv_dt());
+        let mut lin_jac = Vector::zeros();
+        let mut ang_jac1: AngVector<N> = na::zero();
+        let mut ang_jac2: AngVector<N> = na::zero();
+
+        for i in 0..DIM {
+            if motor_coupled_axes & (1 << i) != 0 {
+                let coeff = self.basis.column(i).dot(&self.lin_err);
+                lin_jac += self.basis.column(i) * coeff;
+                #[cfg(feature = "dim2")]
+                {
+                    ang_jac1 += self.cmat1_basis[i] * coeff;
+                    ang_jac2 += self.cmat2_basis[i] * coeff;
+                }
+                #[cfg(feature = "dim3")]
+                {
+                    ang_jac1 += self.cmat1_basis.column(i) * coeff;
+                    ang_jac2 += self.cmat2_basis.column(i) * coeff;
+                }
+            }
+        }
+
+        let dist = lin_jac.norm();
+        let inv_dist = crate::utils::simd_inv(dist);
+        lin_jac *= inv_dist;
+        ang_jac1 *= inv_dist;
+        ang_jac2 *= inv_dist;
+
+        let mut rhs_wo_bias = N::zero();
+        if motor_params.erp_inv_dt != N::zero() {
+            rhs_wo_bias += (dist - motor_params.target_pos) * motor_params.erp_inv_dt;
+        }
+
+        let mut target_vel = motor_params.target_vel;
+        if let Some(limits) = limits {
+            target_vel =
+                target_vel.simd_clamp((limits[0] - dist) * inv_dt, (limits[1] - dist) * inv_dt);
+        };
+
+        let dvel = lin_jac.dot(&(body2.linvel - body1.linvel))
+            + (ang_jac2.gdot(body2.angvel) - ang_jac1.gdot(body1.angvel));
+        rhs_wo_bias += dvel - target_vel;
+
+        ang_jac2 = body2.sqrt_ii * ang_jac2;
+
+        JointVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2: body2.mj_lambda,
+            im2: body2.im,
+            impulse: N::zero(),
+            impulse_bounds: [-motor_params.max_impulse, motor_params.max_impulse],
+            lin_jac,
+            ang_jac2,
+            inv_lhs: N::zero(), // Will be set during ortogonalization.
+            cfm_coeff: motor_params.cfm_coeff,
+            cfm_gain: motor_params.cfm_gain,
+            rhs: rhs_wo_bias,
+            rhs_wo_bias,
+            writeback_id,
+        }
+    }
 
     pub fn lock_linear_ground<const LANES: usize>(
         &self,
@@ -832,6 +933,7 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         body2: &SolverBody<N, LANES>,
         limited_axis: usize,
         limits: [N; 2],
+        restitution: N,
         writeback_id: WritebackId,
     ) -> JointVelocityGroundConstraint<N, LANES> {
         let zero = N::zero();
@@ -854,7 +956,8 @@ impl<N: WReal> JointVelocityConstraintBuilder<N> {
         #[cfg(feature = "dim3")]
         let ang_jac = self.ang_basis.column(limited_axis).into_owned();
         let dvel = ang_jac.gdot(body2.angvel) - ang_jac.gdot(body1.angvel);
-        let rhs_wo_bias = dvel;
+        let restitution = effective_limit_restitution(params, restitution, dvel);
+        let rhs_wo_bias = dvel * (N::one() + restitution);
 
         let erp_inv_dt = N::splat(params.joint_erp_inv_dt());
         let cfm_coeff = N::splat(params.joint_cfm_coeff());
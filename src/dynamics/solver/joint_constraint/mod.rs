@@ -4,7 +4,9 @@ pub use joint_constraint::AnyJointVelocityConstraint;
 pub use joint_generic_velocity_constraint::{
     JointGenericVelocityConstraint, JointGenericVelocityGroundConstraint,
 };
-pub use joint_velocity_constraint_builder::JointVelocityConstraintBuilder;
+pub use joint_velocity_constraint_builder::{
+    effective_limit_restitution, JointVelocityConstraintBuilder,
+};
 
 mod joint_constraint;
 mod joint_generic_velocity_constraint;
@@ -12,7 +12,9 @@ use crate::dynamics::{
 use crate::geometry::{ContactManifold, ContactManifoldIndex};
 use na::DVector;
 
-use super::{DeltaVel, ParallelInteractionGroups, ParallelVelocitySolver};
+use super::{
+    flag_bodies_in_persistent_contact, DeltaVel, ParallelInteractionGroups, ParallelVelocitySolver,
+};
 
 #[macro_export]
 #[doc(hidden)]
@@ -259,6 +261,17 @@ impl ParallelIslandSolver {
                 .resize(islands.active_island(island_id).len(), DeltaVel::zero());
         }
 
+        if params.velocity_snap_threshold > 0.0 {
+            flag_bodies_in_persistent_contact(
+                island_id,
+                islands,
+                bodies,
+                manifolds,
+                manifold_indices,
+                &mut self.velocity_solver.in_persistent_contact,
+            );
+        }
+
         for _ in 0..num_task_per_island {
             // We use AtomicPtr because it is Send+Sync while *mut is not.
             // See https://internals.rust-lang.org/t/shouldnt-pointers-be-send-sync-or/8818
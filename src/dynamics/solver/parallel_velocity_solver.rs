@@ -14,6 +14,7 @@ use std::sync::atomic::Ordering;
 pub(crate) struct ParallelVelocitySolver {
     pub mj_lambdas: Vec<DeltaVel<Real>>,
     pub generic_mj_lambdas: DVector<Real>,
+    pub in_persistent_contact: Vec<bool>,
 }
 
 impl ParallelVelocitySolver {
@@ -21,6 +22,7 @@ impl ParallelVelocitySolver {
         Self {
             mj_lambdas: Vec::new(),
             generic_mj_lambdas: DVector::zeros(0),
+            in_persistent_contact: Vec::new(),
         }
     }
 
@@ -100,6 +102,19 @@ impl ParallelVelocitySolver {
             };
         }
 
+        macro_rules! solve_joints {
+            ($mj_lambdas: expr, $generic_mj_lambdas: expr) => {
+                solve!(
+                    joint_constraints,
+                    &joint_constraints.generic_jacobians,
+                    $mj_lambdas,
+                    $generic_mj_lambdas
+                );
+                shift += joint_descs.len();
+                start_index -= joint_descs.len();
+            };
+        }
+
         /*
          * Solve constraints.
          */
@@ -108,15 +123,11 @@ impl ParallelVelocitySolver {
                 let solve_friction = params.interleave_restitution_and_friction_resolution
                     && params.max_velocity_friction_iterations + i
                         >= params.max_velocity_iterations;
-                // Solve joints.
-                solve!(
-                    joint_constraints,
-                    &joint_constraints.generic_jacobians,
-                    &mut self.mj_lambdas,
-                    &mut self.generic_mj_lambdas
-                );
-                shift += joint_descs.len();
-                start_index -= joint_descs.len();
+                let joints_first = params.joint_contact_order.joints_first(i);
+
+                if joints_first {
+                    solve_joints!(&mut self.mj_lambdas, &mut self.generic_mj_lambdas);
+                }
 
                 // Solve rigid-body contacts.
                 solve!(
@@ -154,6 +165,10 @@ impl ParallelVelocitySolver {
                     shift += contact_descs.len();
                     start_index -= contact_descs.len();
                 }
+
+                if !joints_first {
+                    solve_joints!(&mut self.mj_lambdas, &mut self.generic_mj_lambdas);
+                }
             }
 
             // Solve the remaining friction iterations.
@@ -215,6 +230,11 @@ impl ParallelVelocitySolver {
                         new_vels.linvel += dvel.linear;
                         new_vels.angvel += dangvel;
                         new_vels = new_vels.apply_damping(params.dt, &rb.damping);
+                        if params.velocity_snap_threshold > 0.0
+                            && self.in_persistent_contact[rb.ids.active_set_offset]
+                        {
+                            new_vels = new_vels.snap_to_zero(params.velocity_snap_threshold);
+                        }
                         rb.pos.next_position = new_vels.integrate(
                             params.dt,
                             &rb.pos.position,
@@ -248,15 +268,12 @@ impl ParallelVelocitySolver {
 
         // Stabiliziton resolution.
         {
-            for _ in 0..params.max_stabilization_iterations {
-                solve!(
-                    joint_constraints,
-                    &joint_constraints.generic_jacobians,
-                    &mut self.mj_lambdas,
-                    &mut self.generic_mj_lambdas
-                );
-                shift += joint_descs.len();
-                start_index -= joint_descs.len();
+            for i in 0..params.max_stabilization_iterations {
+                let joints_first = params.joint_contact_order.joints_first(i);
+
+                if joints_first {
+                    solve_joints!(&mut self.mj_lambdas, &mut self.generic_mj_lambdas);
+                }
 
                 solve!(
                     contact_constraints,
@@ -279,6 +296,10 @@ impl ParallelVelocitySolver {
                 );
                 shift += contact_descs.len();
                 start_index -= contact_descs.len();
+
+                if !joints_first {
+                    solve_joints!(&mut self.mj_lambdas, &mut self.generic_mj_lambdas);
+                }
             }
         }
 
@@ -310,6 +331,11 @@ impl ParallelVelocitySolver {
                         rb.vels.linvel += dvel.linear;
                         rb.vels.angvel += dangvel;
                         rb.vels = rb.vels.apply_damping(params.dt, &rb.damping);
+                        if params.velocity_snap_threshold > 0.0
+                            && self.in_persistent_contact[rb.ids.active_set_offset]
+                        {
+                            rb.vels = rb.vels.snap_to_zero(params.velocity_snap_threshold);
+                        }
                     }
                 }
             }
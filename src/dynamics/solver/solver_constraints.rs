@@ -126,6 +126,16 @@ impl SolverConstraints<AnyVelocityConstraint> {
             manifold_indices,
         );
 
+        if params.use_block_solver {
+            // The block solver only operates on non-SIMD constraints.
+            self.interaction_groups
+                .nongrouped_interactions
+                .append(&mut self.interaction_groups.grouped_interactions);
+            self.ground_interaction_groups
+                .nongrouped_interactions
+                .append(&mut self.ground_interaction_groups.grouped_interactions);
+        }
+
         let mut jacobian_id = 0;
         #[cfg(feature = "simd-is-enabled")]
         {
@@ -122,6 +122,7 @@ pub(crate) struct VelocityConstraint {
     pub im1: Vector<Real>,
     pub im2: Vector<Real>,
     pub cfm_factor: Real,
+    pub block_solve: bool,
     pub limit: Real,
     pub mj_lambda1: usize,
     pub mj_lambda2: usize,
@@ -191,6 +192,7 @@ impl VelocityConstraint {
                 im1: mprops1.effective_inv_mass,
                 im2: mprops2.effective_inv_mass,
                 cfm_factor,
+                block_solve: params.use_block_solver,
                 limit: 0.0,
                 mj_lambda1,
                 mj_lambda2,
@@ -239,6 +241,7 @@ impl VelocityConstraint {
                 constraint.im1 = mprops1.effective_inv_mass;
                 constraint.im2 = mprops2.effective_inv_mass;
                 constraint.cfm_factor = cfm_factor;
+                constraint.block_solve = params.use_block_solver;
                 constraint.limit = 0.0;
                 constraint.mj_lambda1 = mj_lambda1;
                 constraint.mj_lambda2 = mj_lambda2;
@@ -277,8 +280,10 @@ impl VelocityConstraint {
                     let is_bouncy = manifold_point.is_bouncy() as u32 as Real;
                     let is_resting = 1.0 - is_bouncy;
 
-                    let mut rhs_wo_bias = (1.0 + is_bouncy * manifold_point.restitution)
-                        * (vel1 - vel2).dot(&force_dir1);
+                    let dvel = (vel1 - vel2).dot(&force_dir1);
+                    let restitution = manifold_point
+                        .effective_restitution(dvel, params.restitution_velocity_threshold);
+                    let mut rhs_wo_bias = (1.0 + is_bouncy * restitution) * dvel;
                     rhs_wo_bias += manifold_point.dist.max(0.0) * inv_dt;
                     rhs_wo_bias *= is_bouncy + is_resting;
                     let rhs_bias = /* is_resting
@@ -356,6 +361,20 @@ impl VelocityConstraint {
     ) {
         let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
         let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+        let mut solve_normal = solve_normal;
+
+        if solve_normal && self.block_solve && self.num_contacts == 2 {
+            // Fall back to the sequential resolution if the block solve fails.
+            solve_normal = !VelocityConstraintElement::solve_normal_block(
+                self.cfm_factor,
+                &mut self.elements[..2],
+                &self.dir1,
+                &self.im1,
+                &self.im2,
+                &mut mj_lambda1,
+                &mut mj_lambda2,
+            );
+        }
 
         VelocityConstraintElement::solve_group(
             self.cfm_factor,
@@ -1,5 +1,5 @@
 use super::DeltaVel;
-use crate::math::{AngVector, Vector, DIM};
+use crate::math::{AngVector, Real, Vector, DIM};
 use crate::utils::{WBasis, WDot, WReal};
 
 #[derive(Copy, Clone, Debug)]
@@ -209,3 +209,107 @@ impl<N: WReal> VelocityConstraintElement<N> {
         }
     }
 }
+
+impl VelocityConstraintElement<Real> {
+    /// Solves the non-penetration constraints of two contacts simultaneously.
+    ///
+    /// Returns `false`, without modifying anything, if the block system can't be solved. The
+    /// non-penetration constraints must then be solved one after the other.
+    pub fn solve_normal_block(
+        cfm_factor: Real,
+        elements: &mut [Self],
+        dir1: &Vector<Real>,
+        im1: &Vector<Real>,
+        im2: &Vector<Real>,
+        mj_lambda1: &mut DeltaVel<Real>,
+        mj_lambda2: &mut DeltaVel<Real>,
+    ) -> bool {
+        let (part1, part2) = (&elements[0].normal_part, &elements[1].normal_part);
+        let dvel = |part: &VelocityConstraintNormalPart<Real>| {
+            dir1.dot(&mj_lambda1.linear) + part.gcross1.gdot(mj_lambda1.angular)
+                - dir1.dot(&mj_lambda2.linear)
+                + part.gcross2.gdot(mj_lambda2.angular)
+                + part.rhs
+        };
+
+        let lin = dir1.dot(&(im1 + im2).component_mul(dir1));
+        let k11 = lin + part1.gcross1.gdot(part1.gcross1) + part1.gcross2.gdot(part1.gcross2);
+        let k12 = lin + part1.gcross1.gdot(part2.gcross1) + part1.gcross2.gdot(part2.gcross2);
+        let k22 = lin + part2.gcross1.gdot(part2.gcross1) + part2.gcross2.gdot(part2.gcross2);
+
+        let impulses = [part1.impulse, part2.impulse];
+        let new_impulses =
+            match solve_block_lcp2([k11, k12, k22], [dvel(part1), dvel(part2)], impulses) {
+                Some(new_impulses) => new_impulses,
+                None => return false,
+            };
+
+        for (element, (impulse, new_impulse)) in elements
+            .iter_mut()
+            .zip(impulses.iter().zip(new_impulses.iter()))
+        {
+            let part = &mut element.normal_part;
+            let new_impulse = cfm_factor * new_impulse;
+            let dlambda = new_impulse - impulse;
+            part.impulse = new_impulse;
+
+            mj_lambda1.linear += dir1.component_mul(im1) * dlambda;
+            mj_lambda1.angular += part.gcross1 * dlambda;
+
+            mj_lambda2.linear += dir1.component_mul(im2) * -dlambda;
+            mj_lambda2.angular += part.gcross2 * dlambda;
+        }
+
+        true
+    }
+}
+
+/// Solves the linear complementarity problem of two coupled non-penetration constraints.
+///
+/// Given the symmetric matrix `k = [k11, k12, k22]` coupling both constraints, their current
+/// relative normal velocities `dvel`, and their current `impulses`, this finds the new impulses
+/// `x >= 0` such that the resulting relative velocities `v = dvel + k * (x - impulses)` are
+/// non-negative, with `x[i] * v[i] == 0`. The four combinations of active constraints are
+/// tried in turn. Returns `None` if the matrix is ill-conditioned or no combination is valid.
+pub(crate) fn solve_block_lcp2(
+    k: [Real; 3],
+    dvel: [Real; 2],
+    impulses: [Real; 2],
+) -> Option<[Real; 2]> {
+    let [k11, k12, k22] = k;
+    let det = k11 * k22 - k12 * k12;
+
+    if k11 <= 0.0 || k22 <= 0.0 || det <= 1.0e-4 * k11 * k22 {
+        return None;
+    }
+
+    // The relative velocities if both impulses were zero.
+    let b1 = dvel[0] - k11 * impulses[0] - k12 * impulses[1];
+    let b2 = dvel[1] - k12 * impulses[0] - k22 * impulses[1];
+
+    // Both constraints active.
+    let x1 = (k12 * b2 - k22 * b1) / det;
+    let x2 = (k12 * b1 - k11 * b2) / det;
+    if x1 >= 0.0 && x2 >= 0.0 {
+        return Some([x1, x2]);
+    }
+
+    // Only the first constraint active.
+    let x1 = -b1 / k11;
+    if x1 >= 0.0 && k12 * x1 + b2 >= 0.0 {
+        return Some([x1, 0.0]);
+    }
+
+    // Only the second constraint active.
+    let x2 = -b2 / k22;
+    if x2 >= 0.0 && k12 * x2 + b1 >= 0.0 {
+        return Some([0.0, x2]);
+    }
+
+    // No constraint active.
+    if b1 >= 0.0 && b2 >= 0.0 {
+        return Some([0.0, 0.0]);
+    }
+
+    None
+}
@@ -51,6 +51,9 @@ impl WVelocityConstraint {
         let allowed_lin_err = SimdReal::splat(params.allowed_linear_error);
         let erp_inv_dt = SimdReal::splat(params.erp_inv_dt());
         let max_penetration_correction = SimdReal::splat(params.max_penetration_correction);
+        let sq_restitution_threshold = SimdReal::splat(
+            params.restitution_velocity_threshold * params.restitution_velocity_threshold,
+        );
 
         let handles1 = gather![|ii| manifolds[ii].data.rigid_body1.unwrap()];
         let handles2 = gather![|ii| manifolds[ii].data.rigid_body2.unwrap()];
@@ -149,6 +152,10 @@ impl WVelocityConstraint {
                     );
 
                     let projected_velocity = (vel1 - vel2).dot(&force_dir1);
+                    let restitution = restitution.select(
+                        (projected_velocity * projected_velocity).simd_ge(sq_restitution_threshold),
+                        SimdReal::zero(),
+                    );
                     let mut rhs_wo_bias =
                         (SimdReal::splat(1.0) + is_bouncy * restitution) * projected_velocity;
                     rhs_wo_bias += dist.simd_max(SimdReal::zero()) * inv_dt;
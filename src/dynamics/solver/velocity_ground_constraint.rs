@@ -18,6 +18,7 @@ pub(crate) struct VelocityGroundConstraint {
     pub tangent1: Vector<Real>, // One of the friction force directions.
     pub im2: Vector<Real>,
     pub cfm_factor: Real,
+    pub block_solve: bool,
     pub limit: Real,
     pub elements: [VelocityGroundConstraintElement<Real>; MAX_MANIFOLD_POINTS],
 
@@ -83,6 +84,7 @@ impl VelocityGroundConstraint {
                 elements: [VelocityGroundConstraintElement::zero(); MAX_MANIFOLD_POINTS],
                 im2: mprops2.effective_inv_mass,
                 cfm_factor,
+                block_solve: params.use_block_solver,
                 limit: 0.0,
                 mj_lambda2,
                 manifold_id,
@@ -129,6 +131,7 @@ impl VelocityGroundConstraint {
                 }
                 constraint.im2 = mprops2.effective_inv_mass;
                 constraint.cfm_factor = cfm_factor;
+                constraint.block_solve = params.use_block_solver;
                 constraint.limit = 0.0;
                 constraint.mj_lambda2 = mj_lambda2;
                 constraint.manifold_id = manifold_id;
@@ -163,7 +166,9 @@ impl VelocityGroundConstraint {
                     let is_resting = 1.0 - is_bouncy;
 
                     let dvel = (vel1 - vel2).dot(&force_dir1);
-                    let mut rhs_wo_bias = (1.0 + is_bouncy * manifold_point.restitution) * dvel;
+                    let restitution = manifold_point
+                        .effective_restitution(dvel, params.restitution_velocity_threshold);
+                    let mut rhs_wo_bias = (1.0 + is_bouncy * restitution) * dvel;
                     rhs_wo_bias += manifold_point.dist.max(0.0) * inv_dt;
                     rhs_wo_bias *= is_bouncy + is_resting;
                     let rhs_bias = /* is_resting
@@ -234,6 +239,18 @@ impl VelocityGroundConstraint {
         solve_friction: bool,
     ) {
         let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+        let mut solve_normal = solve_normal;
+
+        if solve_normal && self.block_solve && self.num_contacts == 2 {
+            // Fall back to the sequential resolution if the block solve fails.
+            solve_normal = !VelocityGroundConstraintElement::solve_normal_block(
+                self.cfm_factor,
+                &mut self.elements[..2],
+                &self.dir1,
+                &self.im2,
+                &mut mj_lambda2,
+            );
+        }
 
         VelocityGroundConstraintElement::solve_group(
             self.cfm_factor,
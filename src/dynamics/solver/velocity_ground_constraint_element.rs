@@ -1,5 +1,5 @@
-use super::DeltaVel;
-use crate::math::{AngVector, Vector, DIM};
+use super::{solve_block_lcp2, DeltaVel};
+use crate::math::{AngVector, Real, Vector, DIM};
 use crate::utils::{WBasis, WDot, WReal};
 
 #[derive(Copy, Clone, Debug)]
@@ -179,3 +179,49 @@ impl<N: WReal> VelocityGroundConstraintElement<N> {
         }
     }
 }
+
+impl VelocityGroundConstraintElement<Real> {
+    /// Solves the non-penetration constraints of two contacts simultaneously.
+    ///
+    /// Returns `false`, without modifying anything, if the block system can't be solved. The
+    /// non-penetration constraints must then be solved one after the other.
+    pub fn solve_normal_block(
+        cfm_factor: Real,
+        elements: &mut [Self],
+        dir1: &Vector<Real>,
+        im2: &Vector<Real>,
+        mj_lambda2: &mut DeltaVel<Real>,
+    ) -> bool {
+        let (part1, part2) = (&elements[0].normal_part, &elements[1].normal_part);
+        let dvel = |part: &VelocityGroundConstraintNormalPart<Real>| {
+            -dir1.dot(&mj_lambda2.linear) + part.gcross2.gdot(mj_lambda2.angular) + part.rhs
+        };
+
+        let lin = dir1.dot(&im2.component_mul(dir1));
+        let k11 = lin + part1.gcross2.gdot(part1.gcross2);
+        let k12 = lin + part1.gcross2.gdot(part2.gcross2);
+        let k22 = lin + part2.gcross2.gdot(part2.gcross2);
+
+        let impulses = [part1.impulse, part2.impulse];
+        let new_impulses =
+            match solve_block_lcp2([k11, k12, k22], [dvel(part1), dvel(part2)], impulses) {
+                Some(new_impulses) => new_impulses,
+                None => return false,
+            };
+
+        for (element, (impulse, new_impulse)) in elements
+            .iter_mut()
+            .zip(impulses.iter().zip(new_impulses.iter()))
+        {
+            let part = &mut element.normal_part;
+            let new_impulse = cfm_factor * new_impulse;
+            let dlambda = new_impulse - impulse;
+            part.impulse = new_impulse;
+
+            mj_lambda2.linear += dir1.component_mul(im2) * -dlambda;
+            mj_lambda2.angular += part.gcross2 * dlambda;
+        }
+
+        true
+    }
+}
@@ -3,14 +3,59 @@ use crate::dynamics::{
     solver::{AnyVelocityConstraint, DeltaVel},
     IntegrationParameters, IslandManager, JointGraphEdge, MultibodyJointSet, RigidBodySet,
 };
-use crate::geometry::ContactManifold;
+use crate::geometry::{ContactManifold, ContactManifoldIndex};
 use crate::math::Real;
 use crate::utils::WAngularInertia;
 use na::DVector;
 
+/// Flags, for each dynamic rigid-body of the island, whether it is involved in a persistent
+/// contact, i.e., a solver contact that already existed during the last timestep.
+///
+/// Only these bodies have their velocities snapped by `IntegrationParameters::velocity_snap_threshold`.
+pub(crate) fn flag_bodies_in_persistent_contact(
+    island_id: usize,
+    islands: &IslandManager,
+    bodies: &RigidBodySet,
+    manifolds: &[&mut ContactManifold],
+    manifold_indices: &[ContactManifoldIndex],
+    flags: &mut Vec<bool>,
+) {
+    flags.clear();
+    flags.resize(islands.active_island(island_id).len(), false);
+
+    for manifold_id in manifold_indices {
+        let manifold = &manifolds[*manifold_id];
+        if manifold
+            .data
+            .solver_contacts
+            .iter()
+            .all(|contact| contact.is_new)
+        {
+            continue;
+        }
+
+        for handle in [manifold.data.rigid_body1, manifold.data.rigid_body2]
+            .iter()
+            .flatten()
+        {
+            if let Some(rb) = bodies.get(*handle) {
+                if rb.effective_body_type().is_dynamic() && rb.ids.active_island_id == island_id {
+                    if let Some(flag) = flags.get_mut(rb.ids.active_set_offset) {
+                        *flag = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub(crate) struct VelocitySolver {
     pub mj_lambdas: Vec<DeltaVel<Real>>,
     pub generic_mj_lambdas: DVector<Real>,
+    pub velocity_residual: Real,
+    pub in_persistent_contact: Vec<bool>,
+    prev_mj_lambdas: Vec<DeltaVel<Real>>,
+    prev_generic_mj_lambdas: DVector<Real>,
 }
 
 impl VelocitySolver {
@@ -18,6 +63,10 @@ impl VelocitySolver {
         Self {
             mj_lambdas: Vec::new(),
             generic_mj_lambdas: DVector::zeros(0),
+            velocity_residual: 0.0,
+            in_persistent_contact: Vec::new(),
+            prev_mj_lambdas: Vec::new(),
+            prev_generic_mj_lambdas: DVector::zeros(0),
         }
     }
 
@@ -29,12 +78,26 @@ impl VelocitySolver {
         bodies: &mut RigidBodySet,
         multibodies: &mut MultibodyJointSet,
         manifolds_all: &mut [&mut ContactManifold],
+        manifold_indices: &[ContactManifoldIndex],
         joints_all: &mut [JointGraphEdge],
         contact_constraints: &mut [AnyVelocityConstraint],
         generic_contact_jacobians: &DVector<Real>,
         joint_constraints: &mut [AnyJointVelocityConstraint],
         generic_joint_jacobians: &DVector<Real>,
+        compute_residual: bool,
     ) {
+        self.velocity_residual = 0.0;
+        if params.velocity_snap_threshold > 0.0 {
+            flag_bodies_in_persistent_contact(
+                island_id,
+                islands,
+                bodies,
+                manifolds_all,
+                manifold_indices,
+                &mut self.in_persistent_contact,
+            );
+        }
+
         self.mj_lambdas.clear();
         self.mj_lambdas
             .resize(islands.active_island(island_id).len(), DeltaVel::zero());
@@ -74,13 +137,16 @@ impl VelocitySolver {
         for i in 0..params.max_velocity_iterations {
             let solve_friction = params.interleave_restitution_and_friction_resolution
                 && params.max_velocity_friction_iterations + i >= params.max_velocity_iterations;
+            let joints_first = params.joint_contact_order.joints_first(i);
 
-            for constraint in &mut *joint_constraints {
-                constraint.solve(
-                    generic_joint_jacobians,
-                    &mut self.mj_lambdas[..],
-                    &mut self.generic_mj_lambdas,
-                );
+            if compute_residual && i + 1 == params.max_velocity_iterations {
+                self.prev_mj_lambdas.clone_from(&self.mj_lambdas);
+                self.prev_generic_mj_lambdas
+                    .clone_from(&self.generic_mj_lambdas);
+            }
+
+            if joints_first {
+                self.solve_joints(joint_constraints, generic_joint_jacobians);
             }
 
             for constraint in &mut *contact_constraints {
@@ -104,6 +170,14 @@ impl VelocitySolver {
                     );
                 }
             }
+
+            if !joints_first {
+                self.solve_joints(joint_constraints, generic_joint_jacobians);
+            }
+        }
+
+        if compute_residual && params.max_velocity_iterations > 0 {
+            self.velocity_residual = self.compute_velocity_residual(island_id, islands, bodies);
         }
 
         let remaining_friction_iterations =
@@ -159,6 +233,11 @@ impl VelocitySolver {
                 new_vels.linvel += dvel.linear;
                 new_vels.angvel += dangvel;
                 new_vels = new_vels.apply_damping(params.dt, &rb.damping);
+                if params.velocity_snap_threshold > 0.0
+                    && self.in_persistent_contact[rb.ids.active_set_offset]
+                {
+                    new_vels = new_vels.snap_to_zero(params.velocity_snap_threshold);
+                }
                 new_pos.next_position = new_vels.integrate(
                     params.dt,
                     &rb.pos.position,
@@ -176,13 +255,11 @@ impl VelocitySolver {
             constraint.remove_bias_from_rhs();
         }
 
-        for _ in 0..params.max_stabilization_iterations {
-            for constraint in &mut *joint_constraints {
-                constraint.solve(
-                    generic_joint_jacobians,
-                    &mut self.mj_lambdas[..],
-                    &mut self.generic_mj_lambdas,
-                );
+        for i in 0..params.max_stabilization_iterations {
+            let joints_first = params.joint_contact_order.joints_first(i);
+
+            if joints_first {
+                self.solve_joints(joint_constraints, generic_joint_jacobians);
             }
 
             for constraint in &mut *contact_constraints {
@@ -204,6 +281,10 @@ impl VelocitySolver {
                     true,
                 );
             }
+
+            if !joints_first {
+                self.solve_joints(joint_constraints, generic_joint_jacobians);
+            }
         }
 
         // Update velocities.
@@ -230,6 +311,11 @@ impl VelocitySolver {
                 rb.vels.linvel += dvel.linear;
                 rb.vels.angvel += dangvel;
                 rb.vels = rb.vels.apply_damping(params.dt, &rb.damping);
+                if params.velocity_snap_threshold > 0.0
+                    && self.in_persistent_contact[rb.ids.active_set_offset]
+                {
+                    rb.vels = rb.vels.snap_to_zero(params.velocity_snap_threshold);
+                }
             }
         }
 
@@ -242,4 +328,53 @@ impl VelocitySolver {
             constraint.writeback_impulses(manifolds_all);
         }
     }
+
+    /// The largest velocity change performed since `self.prev_mj_lambdas` was recorded.
+    fn compute_velocity_residual(
+        &self,
+        island_id: usize,
+        islands: &IslandManager,
+        bodies: &RigidBodySet,
+    ) -> Real {
+        let mut residual: Real = 0.0;
+
+        for handle in islands.active_island(island_id) {
+            let rb = &bodies[*handle];
+            let offset = rb.ids.active_set_offset;
+            let dvel = self.mj_lambdas[offset] - self.prev_mj_lambdas[offset];
+            // NOTE: `dvel.angular` is scaled by the square root of the inertia tensor.
+            let dangvel = rb
+                .mprops
+                .effective_world_inv_inertia_sqrt
+                .transform_vector(dvel.angular);
+
+            #[cfg(feature = "dim2")]
+            let dangvel_norm = dangvel.abs();
+            #[cfg(feature = "dim3")]
+            let dangvel_norm = dangvel.norm();
+
+            residual = residual.max(dvel.linear.norm()).max(dangvel_norm);
+        }
+
+        if !self.generic_mj_lambdas.is_empty() {
+            residual =
+                residual.max((&self.generic_mj_lambdas - &self.prev_generic_mj_lambdas).amax());
+        }
+
+        residual
+    }
+
+    fn solve_joints(
+        &mut self,
+        joint_constraints: &mut [AnyJointVelocityConstraint],
+        generic_joint_jacobians: &DVector<Real>,
+    ) {
+        for constraint in joint_constraints {
+            constraint.solve(
+                generic_joint_jacobians,
+                &mut self.mj_lambdas[..],
+                &mut self.generic_mj_lambdas,
+            );
+        }
+    }
 }
@@ -0,0 +1,42 @@
+use crate::geometry::{BroadPhase, BroadPhasePairEvent, ColliderHandle, ColliderSet};
+use crate::math::Real;
+
+/// A broad-phase algorithm usable by the `PhysicsPipeline` and the `CollisionPipeline`.
+///
+/// The broad-phase is responsible for finding the pairs of colliders with intersecting
+/// bounding volumes. These pairs are then given to the narrow-phase for the exact
+/// contact computation.
+pub trait BroadPhaseAlgorithm {
+    /// Updates the broad-phase, taking into account the new collider positions.
+    ///
+    /// The pairs of colliders that started or stopped interacting since the last update
+    /// must be pushed into `events`.
+    fn update(
+        &mut self,
+        prediction_distance: Real,
+        colliders: &mut ColliderSet,
+        modified_colliders: &[ColliderHandle],
+        removed_colliders: &[ColliderHandle],
+        events: &mut Vec<BroadPhasePairEvent>,
+    );
+}
+
+impl BroadPhaseAlgorithm for BroadPhase {
+    fn update(
+        &mut self,
+        prediction_distance: Real,
+        colliders: &mut ColliderSet,
+        modified_colliders: &[ColliderHandle],
+        removed_colliders: &[ColliderHandle],
+        events: &mut Vec<BroadPhasePairEvent>,
+    ) {
+        BroadPhase::update(
+            self,
+            prediction_distance,
+            colliders,
+            modified_colliders,
+            removed_colliders,
+            events,
+        )
+    }
+}
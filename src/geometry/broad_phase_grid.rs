@@ -0,0 +1,356 @@
+use crate::geometry::{
+    BroadPhaseAlgorithm, BroadPhasePairEvent, ColliderHandle, ColliderPair, ColliderSet, AABB,
+};
+use crate::math::{Point, Real};
+use parry::bounding_volume::BoundingVolume;
+use parry::utils::hashmap::HashMap;
+
+/// Colliders covering more grid cells than this are not inserted into the grid.
+const MAX_CELLS_PER_PROXY: i64 = 64;
+
+type CellRange = (Point<i32>, Point<i32>);
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+struct GridProxy {
+    aabb: AABB,
+    // The range of cells covered by this proxy, or `None` if it is too
+    // large to be inserted into the grid.
+    cells: Option<CellRange>,
+    neighbors: Vec<ColliderHandle>,
+}
+
+/// A broad-phase based on a uniform grid (spatial hashing).
+///
+/// Each collider is inserted into all the grid cells its AABB intersects, and only the
+/// colliders sharing at least one cell are tested against each other. On scenes made of
+/// lots of densely packed colliders with roughly the same size (e.g. voxels), this performs
+/// less work than the hierarchical SAP of the default `BroadPhase`, as long as the cell width
+/// is of the same order of magnitude as the size of these colliders.
+///
+/// Colliders covering more than a few cells (e.g. the ground) are not inserted into the grid:
+/// they are tested against all the other colliders instead. So this broad-phase doesn't scale
+/// well if the scene contains many large colliders, or colliders with very different sizes.
+/// In these cases, prefer the default `BroadPhase`.
+///
+/// # Crossover with the default `BroadPhase`
+/// Each moving collider costs one test per collider sharing one of its cells, plus one test per
+/// large collider. The SAP instead pays for the endpoints it swaps along each axis, and for
+/// maintaining its regions. So the grid only wins when:
+/// - the colliders are roughly as wide as `cell_width`, so each one covers 1 to
+///   2<sup>DIM</sup> cells and shares them with a handful of neighbors;
+/// - the scene contains at most a few large colliders, since every moving collider is tested
+///   against all of them;
+/// - many colliders move at each timestep, which is when the SAP performs the most swaps.
+///
+/// Past any of these limits, the default `BroadPhase` is faster. The exact crossover depends on
+/// the hardware and the scene: the `Voxels (SAP)` and `Voxels (grid)` scenes of the
+/// `benchmarks2d` and `benchmarks3d` crates compare both broad-phases on a 50x50 voxel grid.
+///
+/// Use it by passing it to `PhysicsPipeline::step` instead of a `BroadPhase`.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct BroadPhaseGrid {
+    cell_width: Real,
+    cells: HashMap<Point<i32>, Vec<ColliderHandle>>,
+    proxies: HashMap<ColliderHandle, GridProxy>,
+    large_proxies: Vec<ColliderHandle>,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    candidates: Vec<ColliderHandle>, // Workspace
+}
+
+impl Default for BroadPhaseGrid {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl BroadPhaseGrid {
+    /// Creates a new empty grid broad-phase with cells of the given width.
+    ///
+    /// For best performances, `cell_width` should be close to the size of a typical collider.
+    pub fn new(cell_width: Real) -> Self {
+        assert!(cell_width > 0.0, "The grid cell width must be positive.");
+        Self {
+            cell_width,
+            cells: HashMap::default(),
+            proxies: HashMap::default(),
+            large_proxies: Vec::new(),
+            candidates: Vec::new(),
+        }
+    }
+
+    /// The width of the cells of this grid.
+    pub fn cell_width(&self) -> Real {
+        self.cell_width
+    }
+
+    /// The number of colliders managed by this broad-phase.
+    pub fn len(&self) -> usize {
+        self.proxies.len()
+    }
+
+    /// Is this broad-phase empty?
+    pub fn is_empty(&self) -> bool {
+        self.proxies.is_empty()
+    }
+
+    fn cell_range(&self, aabb: &AABB) -> Option<CellRange> {
+        let key = |pt: Point<Real>| -> Point<i32> {
+            (pt / self.cell_width)
+                .coords
+                .map(|e| e.floor() as i32)
+                .into()
+        };
+        let (mins, maxs) = (key(aabb.mins), key(aabb.maxs));
+        let num_cells = mins.iter().zip(maxs.iter()).fold(1i64, |acc, (min, max)| {
+            acc.saturating_mul(*max as i64 - *min as i64 + 1)
+        });
+
+        if num_cells > MAX_CELLS_PER_PROXY {
+            None
+        } else {
+            Some((mins, maxs))
+        }
+    }
+
+    fn insert_proxy(&mut self, handle: ColliderHandle, cells: Option<CellRange>) {
+        match cells {
+            Some(range) => for_each_cell(range, |key| {
+                self.cells.entry(key).or_insert_with(Vec::new).push(handle)
+            }),
+            None => self.large_proxies.push(handle),
+        }
+    }
+
+    fn remove_proxy(&mut self, handle: ColliderHandle, cells: Option<CellRange>) {
+        match cells {
+            Some(range) => for_each_cell(range, |key| {
+                if let Some(cell) = self.cells.get_mut(&key) {
+                    if let Some(i) = cell.iter().position(|h| *h == handle) {
+                        cell.swap_remove(i);
+                    }
+
+                    if cell.is_empty() {
+                        self.cells.remove(&key);
+                    }
+                }
+            }),
+            None => self.large_proxies.retain(|h| *h != handle),
+        }
+    }
+
+    fn handle_removed_collider(&mut self, handle: ColliderHandle) {
+        if let Some(proxy) = self.proxies.remove(&handle) {
+            self.remove_proxy(handle, proxy.cells);
+
+            // NOTE: like the default broad-phase, we don't report the pairs
+            //       involving removed colliders. The narrow-phase takes care of them.
+            for neighbor in &proxy.neighbors {
+                if let Some(neighbor) = self.proxies.get_mut(neighbor) {
+                    neighbor.neighbors.retain(|h| *h != handle);
+                }
+            }
+        }
+    }
+
+    fn handle_modified_collider(&mut self, handle: ColliderHandle, aabb: AABB) {
+        let cells = self.cell_range(&aabb);
+
+        let prev_cells = if let Some(proxy) = self.proxies.get_mut(&handle) {
+            proxy.aabb = aabb;
+            Some(std::mem::replace(&mut proxy.cells, cells))
+        } else {
+            let proxy = GridProxy {
+                aabb,
+                cells,
+                neighbors: Vec::new(),
+            };
+            self.proxies.insert(handle, proxy);
+            None
+        };
+
+        match prev_cells {
+            Some(prev_cells) if prev_cells == cells => {}
+            Some(prev_cells) => {
+                self.remove_proxy(handle, prev_cells);
+                self.insert_proxy(handle, cells);
+            }
+            None => self.insert_proxy(handle, cells),
+        }
+    }
+
+    fn update_pairs(&mut self, handle: ColliderHandle, events: &mut Vec<BroadPhasePairEvent>) {
+        let proxy = &self.proxies[&handle];
+        let aabb = proxy.aabb;
+
+        self.candidates.clear();
+        match proxy.cells {
+            Some(range) => {
+                let (cells, candidates) = (&self.cells, &mut self.candidates);
+                for_each_cell(range, |key| {
+                    if let Some(cell) = cells.get(&key) {
+                        candidates.extend_from_slice(cell);
+                    }
+                });
+                self.candidates.extend_from_slice(&self.large_proxies);
+            }
+            None => self.candidates.extend(self.proxies.keys().copied()),
+        }
+
+        // Sort the candidates so the pairs are reported in a deterministic order.
+        self.candidates.sort_by_key(|h| h.into_raw_parts());
+        self.candidates.dedup();
+
+        let proxies = &self.proxies;
+        self.candidates
+            .retain(|h| *h != handle && proxies[h].aabb.intersects(&aabb));
+
+        let prev_neighbors = std::mem::take(&mut self.proxies.get_mut(&handle).unwrap().neighbors);
+
+        for neighbor in &prev_neighbors {
+            if !self.candidates.contains(neighbor) {
+                if let Some(proxy) = self.proxies.get_mut(neighbor) {
+                    proxy.neighbors.retain(|h| *h != handle);
+                }
+                events.push(BroadPhasePairEvent::DeletePair(ColliderPair::new(
+                    handle, *neighbor,
+                )));
+            }
+        }
+
+        for neighbor in &self.candidates {
+            if !prev_neighbors.contains(neighbor) {
+                if let Some(proxy) = self.proxies.get_mut(neighbor) {
+                    proxy.neighbors.push(handle);
+                }
+                events.push(BroadPhasePairEvent::AddPair(ColliderPair::new(
+                    handle, *neighbor,
+                )));
+            }
+        }
+
+        self.proxies.get_mut(&handle).unwrap().neighbors = self.candidates.clone();
+    }
+}
+
+impl BroadPhaseAlgorithm for BroadPhaseGrid {
+    fn update(
+        &mut self,
+        prediction_distance: Real,
+        colliders: &mut ColliderSet,
+        modified_colliders: &[ColliderHandle],
+        removed_colliders: &[ColliderHandle],
+        events: &mut Vec<BroadPhasePairEvent>,
+    ) {
+        for handle in removed_colliders {
+            self.handle_removed_collider(*handle);
+        }
+
+        // First, update the AABBs of all the modified colliders, so that
+        // the pairs are computed with the final AABBs afterwards.
+        let mut updated = Vec::with_capacity(modified_colliders.len());
+        for handle in modified_colliders {
+            // NOTE: we use `get` because the collider may no longer
+            //       exist if it has been removed.
+            if let Some(co) = colliders.get(*handle) {
+                if !co.changes.needs_broad_phase_update() {
+                    continue;
+                }
+
+                let aabb = co
+                    .compute_aabb()
+                    .loosened(prediction_distance / 2.0 + co.contact_skin());
+                self.handle_modified_collider(*handle, aabb);
+                updated.push(*handle);
+            }
+        }
+
+        for handle in updated {
+            self.update_pairs(handle, events);
+        }
+    }
+}
+
+fn for_each_cell(range: CellRange, mut f: impl FnMut(Point<i32>)) {
+    let (mins, maxs) = range;
+
+    #[cfg(feature = "dim2")]
+    for i in mins.x..=maxs.x {
+        for j in mins.y..=maxs.y {
+            f(Point::new(i, j))
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    for i in mins.x..=maxs.x {
+        for j in mins.y..=maxs.y {
+            for k in mins.z..=maxs.z {
+                f(Point::new(i, j, k))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::{BroadPhase, BroadPhaseAlgorithm, BroadPhaseGrid, ColliderBuilder};
+    use crate::math::{Real, Vector};
+    use crate::pipeline::test_world::TestWorld;
+
+    #[test]
+    fn grid_broad_phase_finds_the_same_contacts() {
+        fn run(broad_phase: &mut dyn BroadPhaseAlgorithm) -> usize {
+            let mut world = TestWorld::new();
+
+            world
+                .colliders
+                .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+
+            // A wall of touching balls.
+            for i in 0..10 {
+                for j in 0..10 {
+                    let handle = world.bodies.insert(
+                        RigidBodyBuilder::dynamic()
+                            .translation(Vector::x() * i as Real + Vector::y() * (j as Real + 0.5))
+                            .build(),
+                    );
+                    world.colliders.insert_with_parent(
+                        ColliderBuilder::ball(0.5).build(),
+                        handle,
+                        &mut world.bodies,
+                    );
+                }
+            }
+
+            // Step with the given broad-phase instead of the default one of the world.
+            for _ in 0..5 {
+                world.pipeline.step(
+                    &Vector::zeros(),
+                    &world.integration_parameters,
+                    &mut world.islands,
+                    broad_phase,
+                    &mut world.narrow_phase,
+                    &mut world.bodies,
+                    &mut world.colliders,
+                    &mut world.impulse_joints,
+                    &mut world.multibody_joints,
+                    &mut world.ccd,
+                    &(),
+                    &(),
+                );
+            }
+
+            let narrow_phase = &world.narrow_phase;
+            assert!(
+                narrow_phase.num_contact_pairs() >= narrow_phase.active_contact_pairs().count()
+            );
+            narrow_phase.active_contact_pairs().count()
+        }
+
+        let num_contacts = run(&mut BroadPhase::new());
+        assert!(num_contacts > 0);
+        assert_eq!(run(&mut BroadPhaseGrid::new(1.0)), num_contacts);
+    }
+}
@@ -4,7 +4,7 @@ use super::{
 use crate::geometry::broad_phase_multi_sap::SAPProxyIndex;
 use crate::geometry::{
     ColliderBroadPhaseData, ColliderChanges, ColliderHandle, ColliderPosition, ColliderSet,
-    ColliderShape,
+    ColliderShape, AABB,
 };
 use crate::math::Real;
 use crate::utils::IndexMut2;
@@ -344,13 +344,13 @@ impl BroadPhase {
         prediction_distance: Real,
         handle: ColliderHandle,
         proxy_index: &mut u32,
-        collider: (&ColliderPosition, &ColliderShape, &ColliderChanges),
+        collider: (&ColliderPosition, &ColliderShape, &ColliderChanges, Real),
     ) -> bool {
-        let (co_pos, co_shape, co_changes) = collider;
+        let (co_pos, co_shape, co_changes, co_contact_skin) = collider;
 
         let mut aabb = co_shape
             .compute_aabb(co_pos)
-            .loosened(prediction_distance / 2.0);
+            .loosened(prediction_distance / 2.0 + co_contact_skin);
 
         aabb.mins = super::clamp_point(aabb.mins);
         aabb.maxs = super::clamp_point(aabb.maxs);
@@ -433,6 +433,56 @@ impl BroadPhase {
         need_region_propagation
     }
 
+    /// The colliders with a broad-phase AABB intersecting `aabb`, sorted by handle.
+    ///
+    /// Only the SAP regions intersecting `aabb` are traversed, on each layer. The broad-phase
+    /// AABBs are enlarged by the prediction distance, so this may report colliders that don't
+    /// actually intersect `aabb`.
+    pub(crate) fn colliders_intersecting_aabb(&self, aabb: &AABB) -> Vec<ColliderHandle> {
+        let mut result = Vec::new();
+
+        for layer in &self.layers {
+            layer.for_each_region_intersecting_aabb(aabb, |region_id| {
+                let region = match &self.proxies[region_id].data {
+                    SAPProxyData::Region(Some(region)) => region,
+                    _ => return,
+                };
+
+                // Each proxy of the region has exactly one start endpoint on each axis.
+                for endpoint in &region.axes[0].endpoints {
+                    if endpoint.is_sentinel() || !endpoint.is_start() {
+                        continue;
+                    }
+
+                    let proxy = &self.proxies[endpoint.proxy()];
+                    if let SAPProxyData::Collider(handle) = proxy.data {
+                        if proxy.aabb.intersects(aabb) {
+                            result.push(handle);
+                        }
+                    }
+                }
+            });
+        }
+
+        // A proxy may be part of several regions.
+        result.sort_unstable_by_key(|h| h.into_raw_parts());
+        result.dedup();
+        result
+    }
+
+    /// Calls `callback` for each collider with a broad-phase AABB intersecting `aabb`.
+    ///
+    /// The traversal stops as soon as `callback` returns `false`. The results are conservative:
+    /// the broad-phase AABBs are enlarged by the prediction distance and only updated by
+    /// `Self::update`, so this may report colliders that don't actually intersect `aabb`.
+    pub fn query_aabb(&self, aabb: &AABB, mut callback: impl FnMut(ColliderHandle) -> bool) {
+        for handle in self.colliders_intersecting_aabb(aabb) {
+            if !callback(handle) {
+                break;
+            }
+        }
+    }
+
     /// Updates the broad-phase, taking into account the new collider positions.
     pub fn update(
         &mut self,
@@ -461,7 +511,7 @@ impl BroadPhase {
                     prediction_distance,
                     *handle,
                     &mut new_proxy_id,
-                    (&co.pos, &co.shape, &co.changes),
+                    (&co.pos, &co.shape, &co.changes, co.contact_skin),
                 ) {
                     need_region_propagation = true;
                 }
@@ -606,7 +656,10 @@ mod test {
     use crate::dynamics::{
         ImpulseJointSet, IslandManager, MultibodyJointSet, RigidBodyBuilder, RigidBodySet,
     };
-    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet};
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, AABB};
+    use crate::math::{Point, Real, Vector};
+    use crate::pipeline::test_world::TestWorld;
+    use parry::bounding_volume::BoundingVolume;
 
     #[test]
     fn test_add_update_remove() {
@@ -644,4 +697,44 @@ mod test {
         // Make sure the proxy handles is recycled properly.
         broad_phase.update(0.0, &mut colliders, &[coh], &[], &mut events);
     }
+
+    #[test]
+    fn colliders_in_aabb_only_reports_nearby_colliders() {
+        let mut world = TestWorld::new();
+
+        for i in 0..30 {
+            for j in 0..30 {
+                let position = Vector::x() * i as Real + Vector::y() * j as Real;
+                world
+                    .colliders
+                    .insert(ColliderBuilder::ball(0.25).translation(position).build());
+            }
+        }
+
+        world.step(&Vector::zeros());
+
+        let (mut mins, mut maxs) = (
+            Point::from(Vector::repeat(-1.0)),
+            Point::from(Vector::repeat(1.0)),
+        );
+        mins.x = 4.5;
+        mins.y = 4.5;
+        maxs.x = 9.5;
+        maxs.y = 9.5;
+        let aabb = AABB::new(mins, maxs);
+        let found: Vec<_> = world
+            .colliders
+            .colliders_in_aabb(&world.broad_phase, &aabb)
+            .collect();
+        let mut expected: Vec<_> = world
+            .colliders
+            .iter()
+            .filter(|(_, co)| co.compute_aabb().intersects(&aabb))
+            .map(|(handle, _)| handle)
+            .collect();
+        expected.sort_by_key(|h| h.into_raw_parts());
+
+        assert_eq!(expected.len(), 25);
+        assert_eq!(found, expected);
+    }
 }
@@ -212,6 +212,49 @@ impl SAPLayer {
         }
     }
 
+    /// Calls `f` with the proxy index of each existing region of this layer intersecting `aabb`.
+    pub fn for_each_region_intersecting_aabb(&self, aabb: &AABB, mut f: impl FnMut(SAPProxyIndex)) {
+        let aabb = AABB::new(super::clamp_point(aabb.mins), super::clamp_point(aabb.maxs));
+        let start = super::point_key(aabb.mins, self.region_width);
+        let end = super::point_key(aabb.maxs, self.region_width);
+        let num_keys = start
+            .iter()
+            .zip(end.iter())
+            .fold(1i64, |acc, (start, end)| {
+                acc.saturating_mul(*end as i64 - *start as i64 + 1)
+            });
+
+        if num_keys > self.regions.len() as i64 {
+            // The AABB covers more keys than there are regions: check all the regions instead.
+            for (key, region_id) in &self.regions {
+                if super::region_aabb(*key, self.region_width).intersects(&aabb) {
+                    f(*region_id)
+                }
+            }
+            return;
+        }
+
+        #[cfg(feature = "dim2")]
+        let k_range = 0..1;
+        #[cfg(feature = "dim3")]
+        let k_range = start.z..=end.z;
+
+        for i in start.x..=end.x {
+            for j in start.y..=end.y {
+                for _k in k_range.clone() {
+                    #[cfg(feature = "dim2")]
+                    let region_key = Point::new(i, j);
+                    #[cfg(feature = "dim3")]
+                    let region_key = Point::new(i, j, _k);
+
+                    if let Some(region_id) = self.regions.get(&region_key) {
+                        f(*region_id)
+                    }
+                }
+            }
+        }
+    }
+
     pub fn preupdate_collider(
         &mut self,
         proxy_id: u32,
@@ -26,6 +26,7 @@ pub struct Collider {
     pub(crate) material: ColliderMaterial,
     pub(crate) flags: ColliderFlags,
     pub(crate) bf_data: ColliderBroadPhaseData,
+    pub(crate) contact_skin: Real,
     /// User-defined data associated to this collider.
     pub user_data: u128,
 }
@@ -235,7 +236,7 @@ impl Collider {
     pub fn density(&self) -> Option<Real> {
         match &self.mprops {
             ColliderMassProps::Density(density) => Some(*density),
-            ColliderMassProps::MassProperties(_) => None,
+            ColliderMassProps::Mass(_) | ColliderMassProps::MassProperties(_) => None,
         }
     }
 
@@ -260,6 +261,25 @@ impl Collider {
         self.shape = shape;
     }
 
+    /// The contact skin of this collider.
+    ///
+    /// This is an extra margin added to the global `IntegrationParameters::prediction_distance`
+    /// for the contacts involving this collider.
+    pub fn contact_skin(&self) -> Real {
+        self.contact_skin
+    }
+
+    /// Sets the contact skin of this collider.
+    ///
+    /// A larger contact skin lets the contacts with this collider be detected earlier, which
+    /// helps preventing fast objects from tunneling through thin colliders.
+    pub fn set_contact_skin(&mut self, skin: Real) {
+        if self.contact_skin != skin {
+            self.changes.insert(ColliderChanges::SHAPE);
+            self.contact_skin = skin;
+        }
+    }
+
     /// Retrieve the SharedShape. Also see the `shape()` function
     pub fn shared_shape(&self) -> &SharedShape {
         &self.shape
@@ -278,10 +298,7 @@ impl Collider {
 
     /// Compute the local-space mass properties of this collider.
     pub fn mass_properties(&self) -> MassProperties {
-        match &self.mprops {
-            ColliderMassProps::Density(density) => self.shape.mass_properties(*density),
-            ColliderMassProps::MassProperties(mass_properties) => **mass_properties,
-        }
+        self.mprops.mass_properties(&*self.shape)
     }
 }
 
@@ -294,6 +311,10 @@ pub struct ColliderBuilder {
     pub shape: SharedShape,
     /// The uniform density of the collider to be built.
     pub density: Option<Real>,
+    /// The mass of the collider to be built.
+    ///
+    /// If set, it takes precedence over `density`.
+    pub mass: Option<Real>,
     /// Overrides automatic computation of `MassProperties`.
     /// If None, it will be computed based on shape and density.
     pub mass_properties: Option<MassProperties>,
@@ -321,6 +342,8 @@ pub struct ColliderBuilder {
     pub collision_groups: InteractionGroups,
     /// The solver groups for the collider being built.
     pub solver_groups: InteractionGroups,
+    /// The contact skin of the collider being built.
+    pub contact_skin: Real,
 }
 
 impl ColliderBuilder {
@@ -329,6 +352,7 @@ impl ColliderBuilder {
         Self {
             shape,
             density: None,
+            mass: None,
             mass_properties: None,
             friction: Self::default_friction(),
             restitution: 0.0,
@@ -337,6 +361,7 @@ impl ColliderBuilder {
             user_data: 0,
             collision_groups: InteractionGroups::all(),
             solver_groups: InteractionGroups::all(),
+            contact_skin: 0.0,
             friction_combine_rule: CoefficientCombineRule::Average,
             restitution_combine_rule: CoefficientCombineRule::Average,
             active_collision_types: ActiveCollisionTypes::default(),
@@ -621,6 +646,15 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets the contact skin of the collider to be created.
+    ///
+    /// This extra margin is added to the global `IntegrationParameters::prediction_distance`
+    /// for the contacts involving this collider. Defaults to zero.
+    pub fn contact_skin(mut self, skin: Real) -> Self {
+        self.contact_skin = skin;
+        self
+    }
+
     /// The set of physics hooks enabled for this collider.
     pub fn active_hooks(mut self, active_hooks: ActiveHooks) -> Self {
         self.active_hooks = active_hooks;
@@ -665,10 +699,24 @@ impl ColliderBuilder {
 
     /// Sets the uniform density of the collider this builder will build.
     ///
-    /// This will be overridden by a call to [`Self::mass_properties`] so it only makes sense to call
-    /// either [`Self::density`] or [`Self::mass_properties`].
+    /// This replaces any mass previously set with [`Self::mass`]. This will be overridden by a
+    /// call to [`Self::mass_properties`] so it only makes sense to call either [`Self::density`]
+    /// or [`Self::mass_properties`].
     pub fn density(mut self, density: Real) -> Self {
         self.density = Some(density);
+        self.mass = None;
+        self
+    }
+
+    /// Sets the mass of the collider this builder will build.
+    ///
+    /// This replaces any density previously set with [`Self::density`]. The angular inertia
+    /// is computed from the shape and scaled to match this mass, and the center-of-mass is
+    /// derived from the shape only. This will be overridden by a call to
+    /// [`Self::mass_properties`].
+    pub fn mass(mut self, mass: Real) -> Self {
+        self.mass = Some(mass);
+        self.density = None;
         self
     }
 
@@ -736,6 +784,7 @@ impl ColliderBuilder {
             bf_data,
             flags,
             coll_type,
+            contact_skin: self.contact_skin,
             user_data: self.user_data,
         }
     }
@@ -755,6 +804,8 @@ impl ColliderBuilder {
     ) {
         let mass_info = if let Some(mp) = self.mass_properties {
             ColliderMassProps::MassProperties(Box::new(mp))
+        } else if let Some(mass) = self.mass {
+            ColliderMassProps::Mass(mass)
         } else {
             let default_density = Self::default_density();
             let density = self.density.unwrap_or(default_density);
@@ -3,6 +3,7 @@ use crate::geometry::{InteractionGroups, SAPProxyIndex, Shape, SharedShape};
 use crate::math::{Isometry, Real};
 use crate::parry::partitioning::IndexedData;
 use crate::pipeline::{ActiveEvents, ActiveHooks};
+use na::ComplexField;
 use std::ops::{Deref, DerefMut};
 
 /// The unique identifier of a collider added to a collider set.
@@ -17,6 +18,15 @@ impl ColliderHandle {
         self.0.into_raw_parts()
     }
 
+    /// The index of the slot this handle points to, without its generation.
+    ///
+    /// This index remains valid as long as the object stays in the `ColliderSet`, and can be used to
+    /// store per-object data into a flat array. It may be reused by another object once this one
+    /// is removed. Use `ColliderSet::get_unknown_gen` to retrieve the object from this index.
+    pub fn index(self) -> u32 {
+        self.0.into_raw_parts().0
+    }
+
     /// Reconstructs an handle from its (index, generation) components.
     pub fn from_raw_parts(id: u32, generation: u32) -> Self {
         Self(crate::data::arena::Index::from_raw_parts(id, generation))
@@ -59,9 +69,15 @@ bitflags::bitflags! {
         const TYPE     = 1 << 5; // => NF update. NF pair invalidation.
         /// Flag indicating that the dominance groups of the parent of this collider have been modified.
         ///
-        /// This flags is automatically set by the `PhysicsPipeline` when the `RigidBodyChanges::DOMINANCE`
-        /// or `RigidBodyChanges::TYPE` of the parent rigid-body of this collider is detected.
+        /// This flags is automatically set by the `PhysicsPipeline` when the `RigidBodyChanges::DOMINANCE`,
+        /// `RigidBodyChanges::TYPE`, or `RigidBodyChanges::FROZEN` of the parent rigid-body of this
+        /// collider is detected.
         const PARENT_EFFECTIVE_DOMINANCE = 1 << 6; // NF update.
+        /// Flag indicating that the parent rigid-body of this collider has been enabled or disabled.
+        ///
+        /// This flags is automatically set by the `PhysicsPipeline` when the `RigidBodyChanges::ENABLED_OR_DISABLED`
+        /// of the parent rigid-body of this collider is detected.
+        const PARENT_ENABLED = 1 << 7; // NF update.
     }
 }
 
@@ -134,6 +150,12 @@ pub enum ColliderMassProps {
     /// Its actual `MassProperties` are computed automatically with
     /// the help of [`SharedShape::mass_properties`].
     Density(Real),
+    /// The collider is given a mass.
+    ///
+    /// Its actual `MassProperties` are computed automatically with the help of
+    /// [`SharedShape::mass_properties`], and scaled so that their mass matches this value.
+    /// The center-of-mass only depends on the shape.
+    Mass(Real),
     /// The collider is given explicit mass-properties.
     MassProperties(Box<MassProperties>),
 }
@@ -153,13 +175,25 @@ impl From<MassProperties> for ColliderMassProps {
 impl ColliderMassProps {
     /// The mass-properties of this collider.
     ///
-    /// If `self` is the `Density` variant, then this computes the mass-properties based
+    /// If `self` is the `Density` or `Mass` variant, then this computes the mass-properties based
     /// on the given shape.
     ///
     /// If `self` is the `MassProperties` variant, then this returns the stored mass-properties.
     pub fn mass_properties(&self, shape: &dyn Shape) -> MassProperties {
         match self {
             Self::Density(density) => shape.mass_properties(*density),
+            Self::Mass(mass) => {
+                let mut mprops = shape.mass_properties(1.0);
+
+                if mprops.inv_mass != 0.0 {
+                    // The angular inertia is proportional to the mass.
+                    mprops.inv_principal_inertia_sqrt *=
+                        crate::utils::inv(ComplexField::sqrt(*mass * mprops.inv_mass));
+                    mprops.inv_mass = crate::utils::inv(*mass);
+                }
+
+                mprops
+            }
             Self::MassProperties(mprops) => **mprops,
         }
     }
@@ -1,6 +1,9 @@
 use crate::data::arena::Arena;
 use crate::dynamics::{IslandManager, RigidBodyHandle, RigidBodySet};
-use crate::geometry::{Collider, ColliderChanges, ColliderHandle, ColliderParent};
+use crate::geometry::{
+    BroadPhase, Collider, ColliderChanges, ColliderHandle, ColliderParent, InteractionGroups,
+    SharedShape, AABB,
+};
 use crate::math::Isometry;
 use std::ops::{Index, IndexMut};
 
@@ -52,6 +55,26 @@ impl ColliderSet {
         })
     }
 
+    /// Iterates through the colliders with an AABB intersecting `aabb`, as seen by the broad-phase.
+    ///
+    /// This is a conservative test: the broad-phase AABBs are slightly enlarged, and they
+    /// are only updated during a timestep. Exact tests have to be performed by the caller
+    /// on the returned candidates if needed.
+    ///
+    /// Only the broad-phase regions intersecting `aabb` are traversed, so the cost depends on
+    /// the number of colliders close to `aabb` rather than on the total number of colliders.
+    /// The colliders are yielded in a deterministic order, sorted by handle.
+    pub fn colliders_in_aabb<'a>(
+        &'a self,
+        broad_phase: &'a BroadPhase,
+        aabb: &'a AABB,
+    ) -> impl Iterator<Item = ColliderHandle> + 'a {
+        broad_phase
+            .colliders_intersecting_aabb(aabb)
+            .into_iter()
+            .filter(move |handle| self.contains(*handle))
+    }
+
     /// The number of colliders on this set.
     pub fn len(&self) -> usize {
         self.colliders.len()
@@ -119,7 +142,33 @@ impl ColliderSet {
         handle
     }
 
+    /// Inserts several colliders to this set, attach each of them to its rigid-body, and retrieve their handles.
+    ///
+    /// The handles are allocated in the same order as if each collider was inserted with
+    /// `insert_with_parent`. The broad-phase proxies of all these colliders are created in bulk
+    /// during the next timestep.
+    pub fn insert_batch(
+        &mut self,
+        colliders: impl IntoIterator<Item = (Collider, RigidBodyHandle)>,
+        bodies: &mut RigidBodySet,
+    ) -> Vec<ColliderHandle> {
+        let colliders = colliders.into_iter();
+        let mut handles = Vec::with_capacity(colliders.size_hint().0);
+        self.modified_colliders.reserve(colliders.size_hint().0);
+
+        for (coll, parent_handle) in colliders {
+            handles.push(self.insert_with_parent(coll, parent_handle, bodies));
+        }
+
+        handles
+    }
+
     /// Sets the parent of the given collider.
+    ///
+    /// The mass-properties of both the previous and the new parent are updated accordingly.
+    /// If `new_parent_handle` is `None`, the collider becomes a fixed collider at its current
+    /// world-space position. Otherwise, its position relative to the new parent is given by
+    /// `Collider::position_wrt_parent` (or the identity if it didn't have any parent).
     // TODO: find a way to define this as a method of Collider.
     pub fn set_parent(
         &mut self,
@@ -167,6 +216,41 @@ impl ColliderSet {
         }
     }
 
+    /// Sets the shape of the given collider.
+    ///
+    /// The collider is flagged as modified so that its bounding volume and contacts
+    /// are updated by the next timestep. This does nothing if the collider does not exist.
+    pub fn set_shape(&mut self, handle: ColliderHandle, shape: SharedShape) {
+        if let Some(collider) = self.colliders.get_mut(handle.0) {
+            Self::mark_as_modified(handle, collider, &mut self.modified_colliders);
+            collider.set_shape(shape);
+        }
+    }
+
+    /// Sets whether or not the given collider is a sensor.
+    ///
+    /// The collider is flagged as modified so that its contact or intersection pairs are moved
+    /// to the intersection or contact graph by the next timestep. This does nothing if the
+    /// collider does not exist.
+    pub fn set_sensor(&mut self, handle: ColliderHandle, is_sensor: bool) {
+        if let Some(collider) = self.colliders.get_mut(handle.0) {
+            Self::mark_as_modified(handle, collider, &mut self.modified_colliders);
+            collider.set_sensor(is_sensor);
+        }
+    }
+
+    /// Sets the collision groups of the given collider.
+    ///
+    /// The collider is flagged as modified so that the next timestep removes the contacts that
+    /// are now filtered-out, and computes the contacts that are now allowed. This does nothing
+    /// if the collider does not exist.
+    pub fn set_collision_groups(&mut self, handle: ColliderHandle, groups: InteractionGroups) {
+        if let Some(collider) = self.colliders.get_mut(handle.0) {
+            Self::mark_as_modified(handle, collider, &mut self.modified_colliders);
+            collider.set_collision_groups(groups);
+        }
+    }
+
     /// Remove a collider from this set and update its parent accordingly.
     ///
     /// If `wake_up` is `true`, the rigid-body the removed collider is attached to
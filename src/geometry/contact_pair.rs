@@ -4,6 +4,7 @@ use crate::math::{Point, Real, Vector};
 use crate::pipeline::EventHandler;
 use crate::prelude::CollisionEventFlags;
 use parry::query::ContactManifoldsWorkspace;
+use parry::utils::IsometryOpt;
 
 use super::CollisionEvent;
 
@@ -114,6 +115,12 @@ pub struct ContactPair {
     /// The set of contact manifolds between the two colliders.
     ///
     /// All contact manifold contain themselves contact points between the colliders.
+    ///
+    /// Contacts are tracked across timesteps by the narrow-phase: a manifold is identified by
+    /// its `subshape1` and `subshape2` indices, and a contact point by its `fid1` and `fid2`
+    /// feature identifiers. As long as a contact persists, these identifiers remain the same,
+    /// so `(collider1, collider2, subshape1, subshape2, fid1, fid2)` can be used as a stable key
+    /// for correlating a contact from one frame to the next.
     pub manifolds: Vec<ContactManifold>,
     /// Is there any active contact in this contact pair?
     pub has_any_active_contact: bool,
@@ -141,6 +148,29 @@ impl ContactPair {
         self.workspace = None;
     }
 
+    /// The contact manifolds between the two colliders of this pair.
+    pub fn manifolds(&self) -> &[ContactManifold] {
+        &self.manifolds
+    }
+
+    /// Computes the world-space locations of the given contact on both colliders.
+    ///
+    /// The `manifold` and `contact` must be part of `self`. The first returned point
+    /// lies on `self.collider1` and the second one on `self.collider2`. Returns `None`
+    /// if one of the colliders no longer exists.
+    pub fn world_contact_points(
+        &self,
+        colliders: &ColliderSet,
+        manifold: &ContactManifold,
+        contact: &Contact,
+    ) -> Option<(Point<Real>, Point<Real>)> {
+        let co1 = colliders.get(self.collider1)?;
+        let co2 = colliders.get(self.collider2)?;
+        let world_pos1 = manifold.subshape_pos1.prepend_to(&co1.pos);
+        let world_pos2 = manifold.subshape_pos2.prepend_to(&co2.pos);
+        Some((world_pos1 * contact.local_p1, world_pos2 * contact.local_p2))
+    }
+
     /// Finds the contact with the smallest signed distance.
     ///
     /// If the colliders involved in this contact pair are penetrating, then
@@ -284,6 +314,17 @@ impl SolverContact {
             self.restitution >= 1.0
         }
     }
+
+    /// The restitution coefficient to apply to this contact, given the relative normal velocity.
+    ///
+    /// This is zero if the relative normal velocity is smaller than `threshold` in absolute value.
+    pub(crate) fn effective_restitution(&self, normal_vel: Real, threshold: Real) -> Real {
+        if normal_vel.abs() < threshold {
+            0.0
+        } else {
+            self.restitution
+        }
+    }
 }
 
 impl Default for ContactManifoldData {
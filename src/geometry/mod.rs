@@ -1,5 +1,7 @@
 //! Structures related to geometry: colliders, shapes, etc.
 
+pub use self::broad_phase_algorithm::BroadPhaseAlgorithm;
+pub use self::broad_phase_grid::BroadPhaseGrid;
 pub use self::broad_phase_multi_sap::{BroadPhase, BroadPhasePairEvent, ColliderPair};
 pub use self::collider_components::*;
 pub use self::contact_pair::{
@@ -132,6 +134,8 @@ pub(crate) fn default_query_dispatcher() -> std::sync::Arc<dyn parry::query::Que
     std::sync::Arc::new(parry::query::DefaultQueryDispatcher)
 }
 
+mod broad_phase_algorithm;
+mod broad_phase_grid;
 mod broad_phase_multi_sap;
 mod collider_components;
 mod contact_pair;
@@ -3,19 +3,21 @@ use rayon::prelude::*;
 
 use crate::data::Coarena;
 use crate::dynamics::{
-    CoefficientCombineRule, IslandManager, RigidBodyDominance, RigidBodySet, RigidBodyType,
+    CoefficientCombineRule, IslandManager, RigidBodyDominance, RigidBodyHandle, RigidBodySet,
+    RigidBodyType,
 };
 use crate::geometry::{
     BroadPhasePairEvent, ColliderChanges, ColliderGraphIndex, ColliderHandle, ColliderPair,
     ColliderSet, CollisionEvent, ContactData, ContactManifold, ContactManifoldData, ContactPair,
-    InteractionGraph, IntersectionPair, SolverContact, SolverFlags,
+    InteractionGraph, InteractionGroups, IntersectionPair, SolverContact, SolverFlags,
 };
-use crate::math::{Real, Vector};
+use crate::math::{Point, Real, Vector};
 use crate::pipeline::{
     ActiveEvents, ActiveHooks, ContactModificationContext, EventHandler, PairFilterContext,
     PhysicsHooks,
 };
 use crate::prelude::CollisionEventFlags;
+use na::{ComplexField, Unit};
 use parry::query::{DefaultQueryDispatcher, PersistentQueryDispatcher};
 use parry::utils::IsometryOpt;
 use std::collections::HashMap;
@@ -171,7 +173,7 @@ impl NarrowPhase {
     ///
     /// If this returns `None`, there is no contact between the two colliders.
     /// If this returns `Some`, then there may be a contact between the two colliders. Check the
-    /// result [`ContactPair::has_any_active_collider`] method to see if there is an actual contact.
+    /// result [`ContactPair::has_any_active_contact`] field to see if there is an actual contact.
     pub fn contact_pair_unknown_gen(&self, collider1: u32, collider2: u32) -> Option<&ContactPair> {
         let id1 = self.graph_indices.get_unknown_gen(collider1)?;
         let id2 = self.graph_indices.get_unknown_gen(collider2)?;
@@ -184,7 +186,7 @@ impl NarrowPhase {
     ///
     /// If this returns `None`, there is no contact between the two colliders.
     /// If this returns `Some`, then there may be a contact between the two colliders. Check the
-    /// result [`ContactPair::has_any_active_collider`] method to see if there is an actual contact.
+    /// result [`ContactPair::has_any_active_contact`] field to see if there is an actual contact.
     pub fn contact_pair(
         &self,
         collider1: ColliderHandle,
@@ -197,6 +199,49 @@ impl NarrowPhase {
             .map(|c| c.2)
     }
 
+    /// The contacts supporting the given rigid-body against gravity, e.g., for ground detection.
+    ///
+    /// Returns, for each contact manifold with at least one active contact involving one of the
+    /// colliders of `body`, the other collider and the world-space contact normal pointing toward
+    /// `body`, if the angle between this normal and `-gravity_dir` is smaller than `max_slope_angle`.
+    /// This relies on the contacts computed by the last timestep.
+    pub fn support_contacts(
+        &self,
+        body: RigidBodyHandle,
+        bodies: &RigidBodySet,
+        gravity_dir: &Unit<Vector<Real>>,
+        max_slope_angle: Real,
+    ) -> Vec<(ColliderHandle, Vector<Real>)> {
+        let min_cos = ComplexField::cos(max_slope_angle);
+        let mut result = vec![];
+
+        if let Some(rb) = bodies.get(body) {
+            for collider in rb.colliders() {
+                for pair in self.contacts_with(*collider) {
+                    let (other, sign) = if pair.collider1 == *collider {
+                        (pair.collider2, -1.0)
+                    } else {
+                        (pair.collider1, 1.0)
+                    };
+
+                    for manifold in &pair.manifolds {
+                        if manifold.data.solver_contacts.is_empty() {
+                            continue;
+                        }
+
+                        // The manifold normal points toward the second collider.
+                        let normal = manifold.data.normal * sign;
+                        if -normal.dot(gravity_dir) >= min_cos {
+                            result.push((other, normal));
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
     /// The intersection pair involving two specific colliders.
     ///
     /// It is strongly recommended to use the [`NarrowPhase::intersection_pair`] method instead. This
@@ -229,10 +274,27 @@ impl NarrowPhase {
     }
 
     /// All the contact pairs maintained by this narrow-phase.
+    ///
+    /// This includes the pairs with colliders that are close but not touching. Use
+    /// `Self::active_contact_pairs` to only get the pairs seen by the constraints solver.
     pub fn contact_pairs(&self) -> impl Iterator<Item = &ContactPair> {
         self.contact_graph.interactions()
     }
 
+    /// The number of contact pairs maintained by this narrow-phase.
+    pub fn num_contact_pairs(&self) -> usize {
+        self.contact_graph.graph.edges.len()
+    }
+
+    /// All the contact pairs with at least one active contact.
+    ///
+    /// After a timestep, these are the contact pairs that were taken into account by the
+    /// constraints solver (unless their colliders belong to sleeping or non-dynamic rigid-bodies).
+    pub fn active_contact_pairs(&self) -> impl Iterator<Item = &ContactPair> {
+        self.contact_pairs()
+            .filter(|pair| pair.has_any_active_contact)
+    }
+
     /// All the intersection pairs maintained by this narrow-phase.
     pub fn intersection_pairs(
         &self,
@@ -546,12 +608,16 @@ impl NarrowPhase {
                                     islands.wake_up(bodies, co_parent2.handle, true);
                                 }
                             }
+                        }
 
-                            if (co1.flags.active_events | co2.flags.active_events)
+                        // NOTE: check the start event instead of the active contacts, because
+                        //       the colliders may be separated by less than the contact event
+                        //       hysteresis distance.
+                        if ctct.start_event_emited
+                            && (co1.flags.active_events | co2.flags.active_events)
                                 .contains(ActiveEvents::COLLISION_EVENTS)
-                            {
-                                ctct.emit_stop_event(bodies, colliders, events);
-                            }
+                        {
+                            ctct.emit_stop_event(bodies, colliders, events);
                         }
                     }
                 }
@@ -668,6 +734,8 @@ impl NarrowPhase {
         bodies: &RigidBodySet,
         colliders: &ColliderSet,
         modified_colliders: &[ColliderHandle],
+        substep: usize,
+        remaining_time: Real,
         hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
@@ -698,13 +766,25 @@ impl NarrowPhase {
                 // TODO: avoid lookup into bodies.
                 let mut rb_type1 = RigidBodyType::Fixed;
                 let mut rb_type2 = RigidBodyType::Fixed;
+                let mut rb_enabled1 = true;
+                let mut rb_enabled2 = true;
 
                 if let Some(co_parent1) = &co1.parent {
-                    rb_type1 = bodies[co_parent1.handle].body_type;
+                    let rb1 = &bodies[co_parent1.handle];
+                    rb_type1 = rb1.body_type;
+                    rb_enabled1 = rb1.enabled;
                 }
 
                 if let Some(co_parent2) = &co2.parent {
-                    rb_type2 = bodies[co_parent2.handle].body_type;
+                    let rb2 = &bodies[co_parent2.handle];
+                    rb_type2 = rb2.body_type;
+                    rb_enabled2 = rb2.enabled;
+                }
+
+                // Filter out the colliders attached to disabled rigid-bodies.
+                if !rb_enabled1 || !rb_enabled2 {
+                    edge.weight.intersecting = false;
+                    break 'emit_events;
                 }
 
                 // Filter based on the rigid-body types.
@@ -731,6 +811,8 @@ impl NarrowPhase {
                         rigid_body2: co2.parent.map(|p| p.handle),
                         collider1: handle1,
                         collider2: handle2,
+                        substep,
+                        remaining_time,
                     };
 
                     if !hooks.filter_intersection_pair(&context) {
@@ -766,9 +848,12 @@ impl NarrowPhase {
     pub(crate) fn compute_contacts(
         &mut self,
         prediction_distance: Real,
+        contact_event_hysteresis: Real,
         bodies: &RigidBodySet,
         colliders: &ColliderSet,
         modified_colliders: &[ColliderHandle],
+        substep: usize,
+        remaining_time: Real,
         hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
@@ -781,9 +866,10 @@ impl NarrowPhase {
         // TODO: don't iterate on all the edges.
         par_iter_mut!(&mut self.contact_graph.graph.edges).for_each(|edge| {
             let pair = &mut edge.weight;
-            let had_any_active_contact = pair.has_any_active_contact;
             let co1 = &colliders[pair.collider1];
             let co2 = &colliders[pair.collider2];
+            // Are the colliders close enough to not emit a stop event?
+            let mut within_event_distance = false;
 
             // TODO: remove the `loop` once labels on blocks are supported.
             'emit_events: loop {
@@ -797,13 +883,25 @@ impl NarrowPhase {
                 // TODO: avoid lookup into bodies.
                 let mut rb_type1 = RigidBodyType::Fixed;
                 let mut rb_type2 = RigidBodyType::Fixed;
+                let mut rb_enabled1 = true;
+                let mut rb_enabled2 = true;
 
                 if let Some(co_parent1) = &co1.parent {
-                    rb_type1 = bodies[co_parent1.handle].body_type;
+                    let rb1 = &bodies[co_parent1.handle];
+                    rb_type1 = rb1.body_type;
+                    rb_enabled1 = rb1.enabled;
                 }
 
                 if let Some(co_parent2) = &co2.parent {
-                    rb_type2 = bodies[co_parent2.handle].body_type;
+                    let rb2 = &bodies[co_parent2.handle];
+                    rb_type2 = rb2.body_type;
+                    rb_enabled2 = rb2.enabled;
+                }
+
+                // Filter out the colliders attached to disabled rigid-bodies.
+                if !rb_enabled1 || !rb_enabled2 {
+                    pair.clear();
+                    break 'emit_events;
                 }
 
                 // Filter based on the rigid-body types.
@@ -830,6 +928,8 @@ impl NarrowPhase {
                         rigid_body2: co2.parent.map(|p| p.handle),
                         collider1: pair.collider1,
                         collider2: pair.collider2,
+                        substep,
+                        remaining_time,
                     };
 
                     if let Some(solver_flags) = hooks.filter_contact_pair(&context) {
@@ -855,14 +955,20 @@ impl NarrowPhase {
                 }
 
                 let pos12 = co1.pos.inv_mul(&co2.pos);
+                let prediction_distance = prediction_distance + co1.contact_skin + co2.contact_skin;
+                let event_distance = prediction_distance + contact_event_hysteresis;
                 let _ = query_dispatcher.contact_manifolds(
                     &pos12,
                     &*co1.shape,
                     &*co2.shape,
-                    prediction_distance,
+                    event_distance,
                     &mut pair.manifolds,
                     &mut pair.workspace,
                 );
+                within_event_distance = pair
+                    .manifolds
+                    .iter()
+                    .any(|m| m.points.iter().any(|pt| pt.dist < event_distance));
 
                 let friction = CoefficientCombineRule::combine(
                     co1.material.friction,
@@ -877,15 +983,17 @@ impl NarrowPhase {
                     co2.material.restitution_combine_rule as u8,
                 );
 
+                // NOTE: the effective groups take the frozen rigid-bodies into account.
                 let zero = RigidBodyDominance(0); // The value doesn't matter, it will be MAX because of the effective groups.
-                let dominance1 = co1
+                let no_parent_group = zero.effective_group(&RigidBodyType::Fixed);
+                let dominance_group1 = co1
                     .parent
-                    .map(|p1| bodies[p1.handle].dominance)
-                    .unwrap_or(zero);
-                let dominance2 = co2
+                    .map(|p1| bodies[p1.handle].effective_dominance_group())
+                    .unwrap_or(no_parent_group);
+                let dominance_group2 = co2
                     .parent
-                    .map(|p2| bodies[p2.handle].dominance)
-                    .unwrap_or(zero);
+                    .map(|p2| bodies[p2.handle].effective_dominance_group())
+                    .unwrap_or(no_parent_group);
 
                 pair.has_any_active_contact = false;
 
@@ -895,8 +1003,7 @@ impl NarrowPhase {
                     manifold.data.rigid_body1 = co1.parent.map(|p| p.handle);
                     manifold.data.rigid_body2 = co2.parent.map(|p| p.handle);
                     manifold.data.solver_flags = solver_flags;
-                    manifold.data.relative_dominance = dominance1.effective_group(&rb_type1)
-                        - dominance2.effective_group(&rb_type2);
+                    manifold.data.relative_dominance = dominance_group1 - dominance_group2;
                     manifold.data.normal = world_pos1 * manifold.local_n1;
 
                     // Generate solver contacts.
@@ -938,6 +1045,8 @@ impl NarrowPhase {
                             rigid_body2: co2.parent.map(|p| p.handle),
                             collider1: pair.collider1,
                             collider2: pair.collider2,
+                            substep,
+                            remaining_time,
                             manifold,
                             solver_contacts: &mut modifiable_solver_contacts,
                             normal: &mut modifiable_normal,
@@ -957,13 +1066,11 @@ impl NarrowPhase {
 
             let active_events = co1.flags.active_events | co2.flags.active_events;
 
-            if pair.has_any_active_contact != had_any_active_contact {
-                if active_events.contains(ActiveEvents::COLLISION_EVENTS) {
-                    if pair.has_any_active_contact {
-                        pair.emit_start_event(bodies, colliders, events);
-                    } else {
-                        pair.emit_stop_event(bodies, colliders, events);
-                    }
+            if active_events.contains(ActiveEvents::COLLISION_EVENTS) {
+                if pair.has_any_active_contact && !pair.start_event_emited {
+                    pair.emit_start_event(bodies, colliders, events);
+                } else if !within_event_distance && pair.start_event_emited {
+                    pair.emit_stop_event(bodies, colliders, events);
                 }
             }
         });
@@ -991,33 +1098,36 @@ impl NarrowPhase {
                     .contains(SolverFlags::COMPUTE_IMPULSES)
                     && manifold.data.num_active_contacts() != 0
                 {
-                    let (active_island_id1, rb_type1, sleeping1) =
+                    let (active_island_id1, rb_type1, sleeping1, solver_groups1) =
                         if let Some(handle1) = manifold.data.rigid_body1 {
                             let rb1 = &bodies[handle1];
                             (
                                 rb1.ids.active_island_id,
-                                rb1.body_type,
+                                rb1.effective_body_type(),
                                 rb1.activation.sleeping,
+                                rb1.solver_groups,
                             )
                         } else {
-                            (0, RigidBodyType::Fixed, true)
+                            (0, RigidBodyType::Fixed, true, InteractionGroups::all())
                         };
 
-                    let (active_island_id2, rb_type2, sleeping2) =
+                    let (active_island_id2, rb_type2, sleeping2, solver_groups2) =
                         if let Some(handle2) = manifold.data.rigid_body2 {
                             let rb2 = &bodies[handle2];
                             (
                                 rb2.ids.active_island_id,
-                                rb2.body_type,
+                                rb2.effective_body_type(),
                                 rb2.activation.sleeping,
+                                rb2.solver_groups,
                             )
                         } else {
-                            (0, RigidBodyType::Fixed, true)
+                            (0, RigidBodyType::Fixed, true, InteractionGroups::all())
                         };
 
                     if (rb_type1.is_dynamic() || rb_type2.is_dynamic())
                         && (!rb_type1.is_dynamic() || !sleeping1)
                         && (!rb_type2.is_dynamic() || !sleeping2)
+                        && solver_groups1.test(solver_groups2)
                     {
                         let island_index = if !rb_type1.is_dynamic() {
                             active_island_id2
@@ -1,6 +1,7 @@
-use crate::dynamics::RigidBodySet;
+use crate::dynamics::{RigidBodyHandle, RigidBodySet};
 use crate::geometry::{ColliderSet, CollisionEvent, ContactPair};
 use crossbeam::channel::Sender;
+use std::sync::Mutex;
 
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -32,7 +33,9 @@ pub trait EventHandler: Send + Sync {
     /// * `colliders` - The set of colliders.
     /// * `contact_pair` - The current state of contacts between the two colliders. This is set ot `None`
     ///                    if at least one of the collider is a sensor (in which case no contact information
-    ///                    is ever computed).
+    ///                    is ever computed). For a `CollisionEvent::Started` event, it contains the contacts
+    ///                    that triggered the event: use `ContactPair::find_deepest_contact` and
+    ///                    `ContactPair::world_contact_points` to find where the contact happened.
     fn handle_collision_event(
         &self,
         bodies: &RigidBodySet,
@@ -40,6 +43,21 @@ pub trait EventHandler: Send + Sync {
         event: CollisionEvent,
         contact_pair: Option<&ContactPair>,
     );
+
+    /// Handle a rigid-body with a NaN or infinite position or velocity.
+    ///
+    /// This is only called if `IntegrationParameters::validate` is `true`, once per substep for
+    /// each invalid active rigid-body. Does nothing by default.
+    fn handle_invalid_body(&self, _bodies: &RigidBodySet, _handle: RigidBodyHandle) {}
+
+    /// Handle a rigid-body falling asleep (`asleep == true`) or waking up (`asleep == false`).
+    ///
+    /// This is called while building the active islands, only when the sleep state actually
+    /// changes: a body remaining asleep or awake never generates redundant events. A wake-up is
+    /// only reported for a body whose falling asleep was reported, so bodies put to sleep
+    /// explicitly by the user (e.g. with `RigidBody::sleep`) don't generate any event.
+    /// Does nothing by default.
+    fn handle_sleep_event(&self, _handle: RigidBodyHandle, _asleep: bool) {}
 }
 
 impl EventHandler for () {
@@ -76,3 +94,52 @@ impl EventHandler for ChannelEventCollector {
         let _ = self.event_sender.send(event);
     }
 }
+
+/// A collision event handler that buffers events so they can be drained after a timestep.
+///
+/// Contact events (involving only non-sensor colliders) and intersection events (involving at
+/// least one sensor collider) are stored into two separate queues.
+#[derive(Default)]
+pub struct CollectorEventHandler {
+    contact_events: Mutex<Vec<CollisionEvent>>,
+    intersection_events: Mutex<Vec<CollisionEvent>>,
+}
+
+impl CollectorEventHandler {
+    /// Initialize a new collision event handler with empty event queues.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes and returns all the contact start/stop events collected so far.
+    pub fn drain_contact_events(&self) -> Vec<CollisionEvent> {
+        std::mem::take(&mut *self.contact_events.lock().unwrap())
+    }
+
+    /// Removes and returns all the intersection start/stop events collected so far.
+    pub fn drain_intersection_events(&self) -> Vec<CollisionEvent> {
+        std::mem::take(&mut *self.intersection_events.lock().unwrap())
+    }
+
+    /// Discards all the events collected so far.
+    pub fn clear(&self) {
+        self.contact_events.lock().unwrap().clear();
+        self.intersection_events.lock().unwrap().clear();
+    }
+}
+
+impl EventHandler for CollectorEventHandler {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: CollisionEvent,
+        _: Option<&ContactPair>,
+    ) {
+        if event.sensor() {
+            self.intersection_events.lock().unwrap().push(event);
+        } else {
+            self.contact_events.lock().unwrap().push(event);
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! Structure for combining the various physics components to perform an actual simulation.
 
 pub use collision_pipeline::CollisionPipeline;
-pub use event_handler::{ActiveEvents, ChannelEventCollector, EventHandler};
+pub use event_handler::{ActiveEvents, ChannelEventCollector, CollectorEventHandler, EventHandler};
+pub use physics_accumulator::PhysicsAccumulator;
 pub use physics_hooks::{ActiveHooks, ContactModificationContext, PairFilterContext, PhysicsHooks};
 pub use physics_pipeline::PhysicsPipeline;
 pub use query_pipeline::{QueryPipeline, QueryPipelineMode};
@@ -14,10 +15,14 @@ pub use self::debug_render_pipeline::{
 
 mod collision_pipeline;
 mod event_handler;
+mod physics_accumulator;
 mod physics_hooks;
 mod physics_pipeline;
 mod query_pipeline;
 mod user_changes;
 
+#[cfg(test)]
+pub(crate) mod test_world;
+
 #[cfg(feature = "debug-render")]
 mod debug_render_pipeline;
@@ -0,0 +1,104 @@
+use crate::math::Real;
+
+/// Helper for running the physics simulation with a fixed timestep, independently from the
+/// frame rate of the application.
+///
+/// The real time elapsed between two frames is accumulated, and consumed by chunks of `dt`
+/// seconds. Each chunk corresponds to one call to `PhysicsPipeline::step` with the
+/// `IntegrationParameters::dt` set to the same `dt` as this accumulator:
+///
+/// ```ignore
+/// let mut accumulator = PhysicsAccumulator::new(integration_parameters.dt, 4);
+/// // For each frame:
+/// for _ in accumulator.advance(frame_dt) {
+///     physics_pipeline.step(&gravity, &integration_parameters, /* … */);
+/// }
+/// let alpha = accumulator.alpha(); // For interpolating the rendered positions.
+/// ```
+///
+/// The number of steps performed for a single frame is capped to avoid a "spiral of death"
+/// where the simulation can never catch up with the real time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct PhysicsAccumulator {
+    dt: Real,
+    max_steps_per_advance: usize,
+    accumulated_time: Real,
+    num_skipped_steps: usize,
+}
+
+impl PhysicsAccumulator {
+    /// Creates a new accumulator consuming the elapsed time by chunks of `dt` seconds.
+    ///
+    /// At most `max_steps_per_advance` steps will be performed for each call to `self.advance`.
+    pub fn new(dt: Real, max_steps_per_advance: usize) -> Self {
+        assert!(dt > 0.0, "The timestep length must be positive.");
+        Self {
+            dt,
+            max_steps_per_advance,
+            accumulated_time: 0.0,
+            num_skipped_steps: 0,
+        }
+    }
+
+    /// The length of each fixed step.
+    pub fn dt(&self) -> Real {
+        self.dt
+    }
+
+    /// The maximum number of steps performed for each call to `self.advance`.
+    pub fn max_steps_per_advance(&self) -> usize {
+        self.max_steps_per_advance
+    }
+
+    /// Sets the maximum number of steps performed for each call to `self.advance`.
+    pub fn set_max_steps_per_advance(&mut self, max_steps: usize) {
+        self.max_steps_per_advance = max_steps;
+    }
+
+    /// Accumulates `real_dt` seconds, and returns an iterator with one item per fixed step to perform.
+    ///
+    /// If more than `self.max_steps_per_advance()` steps are needed to catch up with the real time,
+    /// the extra steps are dropped, and their number is given by `self.num_skipped_steps()`.
+    pub fn advance(&mut self, real_dt: Real) -> std::ops::Range<usize> {
+        self.accumulated_time += real_dt.max(0.0);
+
+        let num_steps = (self.accumulated_time / self.dt).floor() as usize;
+        let performed_steps = num_steps.min(self.max_steps_per_advance);
+        self.num_skipped_steps = num_steps - performed_steps;
+        self.accumulated_time -= num_steps as Real * self.dt;
+
+        0..performed_steps
+    }
+
+    /// The number of steps dropped by the last call to `self.advance`.
+    pub fn num_skipped_steps(&self) -> usize {
+        self.num_skipped_steps
+    }
+
+    /// The fraction of a step, in `[0, 1)`, accumulated but not simulated yet.
+    ///
+    /// This can be used to interpolate the positions rendered between the last two steps.
+    pub fn alpha(&self) -> Real {
+        (self.accumulated_time / self.dt).max(0.0).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::pipeline::PhysicsAccumulator;
+
+    #[test]
+    fn physics_accumulator_caps_catch_up_steps() {
+        let mut accumulator = PhysicsAccumulator::new(0.25, 2);
+
+        assert_eq!(accumulator.advance(0.6).len(), 2);
+        assert_eq!(accumulator.num_skipped_steps(), 0);
+        assert!((accumulator.alpha() - 0.4).abs() < 1.0e-5);
+
+        // A long frame only performs the allowed number of steps.
+        assert_eq!(accumulator.advance(1.0).len(), 2);
+        assert_eq!(accumulator.num_skipped_steps(), 2);
+        assert!((accumulator.alpha() - 0.4).abs() < 1.0e-5);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::dynamics::{RigidBodyHandle, RigidBodySet};
 use crate::geometry::{ColliderHandle, ColliderSet, ContactManifold, SolverContact, SolverFlags};
-use crate::math::{Real, Vector};
+use crate::math::{Point, Real, Vector};
 use na::ComplexField;
 
 /// Context given to custom collision filters to filter-out collisions.
@@ -17,6 +17,16 @@ pub struct PairFilterContext<'a> {
     pub rigid_body1: Option<RigidBodyHandle>,
     /// The handle of the first body involved in the potential collision.
     pub rigid_body2: Option<RigidBodyHandle>,
+    /// The index of the CCD substep this collision-detection is performed for.
+    ///
+    /// This is `0` during the collision-detection at the beginning of the timestep, and `i + 1`
+    /// during the collision-detection performed after the `i`-th CCD substep. A hook can check
+    /// that this is `0` to apply an effect only once per timestep.
+    pub substep: usize,
+    /// The length of the time interval that remains to be simulated during the current timestep.
+    ///
+    /// This is zero after the last substep, as well as outside of `PhysicsPipeline::step`.
+    pub remaining_time: Real,
 }
 
 /// Context given to custom contact modifiers to modify the contacts seen by the constraints solver.
@@ -33,6 +43,16 @@ pub struct ContactModificationContext<'a> {
     pub rigid_body1: Option<RigidBodyHandle>,
     /// The handle of the first body involved in the potential collision.
     pub rigid_body2: Option<RigidBodyHandle>,
+    /// The index of the CCD substep this collision-detection is performed for.
+    ///
+    /// This is `0` during the collision-detection at the beginning of the timestep, and `i + 1`
+    /// during the collision-detection performed after the `i`-th CCD substep. A hook can check
+    /// that this is `0` to apply an effect only once per timestep.
+    pub substep: usize,
+    /// The length of the time interval that remains to be simulated during the current timestep.
+    ///
+    /// This is zero after the last substep, as well as outside of `PhysicsPipeline::step`.
+    pub remaining_time: Real,
     /// The contact manifold.
     pub manifold: &'a ContactManifold,
     /// The solver contacts that can be modified.
@@ -46,6 +66,42 @@ pub struct ContactModificationContext<'a> {
 }
 
 impl<'a> ContactModificationContext<'a> {
+    /// The velocity of `self.rigid_body2` relative to `self.rigid_body1` at the given world-space point.
+    ///
+    /// A collider without parent rigid-body is considered to be static. Comparing this with
+    /// `self.normal` tells whether the colliders are approaching each other at a solver contact.
+    pub fn relative_velocity_at(&self, point: &Point<Real>) -> Vector<Real> {
+        let vel = |handle: Option<RigidBodyHandle>| {
+            handle
+                .and_then(|h| self.bodies.get(h))
+                .map(|rb| rb.velocity_at_point(point))
+                .unwrap_or_else(Vector::zeros)
+        };
+
+        vel(self.rigid_body2) - vel(self.rigid_body1)
+    }
+
+    /// Sets the friction coefficient of all the solver contacts of this manifold.
+    ///
+    /// Setting it to zero emulates, e.g., an ice patch.
+    pub fn set_friction(&mut self, friction: Real) {
+        for contact in self.solver_contacts.iter_mut() {
+            contact.friction = friction;
+        }
+    }
+
+    /// Sets the desired tangent velocity of all the solver contacts of this manifold.
+    ///
+    /// This is the target velocity of the surface of `self.collider2` relative to the surface
+    /// of `self.collider1`, at the contact points. Only its component orthogonal to the contact
+    /// normal is taken into account. Friction will make the colliders reach this relative
+    /// velocity, which emulates, e.g., a conveyor belt.
+    pub fn set_tangent_velocity(&mut self, tangent_velocity: Vector<Real>) {
+        for contact in self.solver_contacts.iter_mut() {
+            contact.tangent_velocity = tangent_velocity;
+        }
+    }
+
     /// Helper function to update `self` to emulate a oneway-platform.
     ///
     /// The "oneway" behavior will only allow contacts between two colliders
@@ -146,12 +202,29 @@ pub trait PhysicsHooks {
     }
 
     /// Applies the intersection pair filter.
+    ///
+    /// Accepts all the intersection pairs by default.
     fn filter_intersection_pair(&self, _context: &PairFilterContext) -> bool {
-        false
+        true
     }
 
     /// Modifies the set of contacts seen by the constraints solver.
     fn modify_solver_contacts(&self, _context: &mut ContactModificationContext) {}
+
+    /// Called once at the beginning of each timestep.
+    fn on_step_begin(&self, _bodies: &mut RigidBodySet, _colliders: &mut ColliderSet) {}
+
+    /// Called after the CCD clamped the motion of some rigid-bodies, before their final positions are set.
+    fn on_ccd_motion_clamped(
+        &self,
+        _dt: Real,
+        _bodies: &mut RigidBodySet,
+        _clamped: &[(RigidBodyHandle, Point<Real>, Vector<Real>)],
+    ) {
+    }
+
+    /// Called right before the forces applied to the active rigid-bodies are combined with gravity.
+    fn on_apply_forces(&self, _bodies: &mut RigidBodySet) {}
 }
 
 /// User-defined functions called by the physics engines during one timestep in order to customize its behavior.
@@ -235,6 +308,38 @@ pub trait PhysicsHooks: Send + Sync {
     ///
     /// The world-space contact normal can be modified in `context.normal`.
     fn modify_solver_contacts(&self, _context: &mut ContactModificationContext) {}
+
+    /// Called once at the beginning of each timestep.
+    ///
+    /// This is called before any modification made by the user to the rigid-bodies and colliders
+    /// is taken into account. So the changes made to `bodies` and `colliders` here will affect
+    /// the timestep that is about to be executed. This can be used, e.g., for resetting some
+    /// per-timestep bookkeeping.
+    fn on_step_begin(&self, _bodies: &mut RigidBodySet, _colliders: &mut ColliderSet) {}
+
+    /// Called after the CCD clamped the motion of some rigid-bodies, before their final positions are set.
+    ///
+    /// Each element of `clamped` contains the handle of a clamped rigid-body, as well as the
+    /// world-space impact point and normal (see `PhysicsPipeline::last_ccd_impacts`). The
+    /// clamped bodies can be modified here, e.g., calling `RigidBody::cancel_ccd_motion_clamping`
+    /// lets a fast projectile go through thin geometry during this substep of length `dt`.
+    fn on_ccd_motion_clamped(
+        &self,
+        _dt: Real,
+        _bodies: &mut RigidBodySet,
+        _clamped: &[(RigidBodyHandle, Point<Real>, Vector<Real>)],
+    ) {
+    }
+
+    /// Called right before the forces applied to the active rigid-bodies are combined with gravity.
+    ///
+    /// This is called once per CCD substep, i.e., only once per timestep if CCD substepping isn't
+    /// enabled (`IntegrationParameters::max_ccd_substeps <= 1`), after the user changes have been
+    /// taken into account. It is not called again for each solver substep
+    /// (`IntegrationParameters::num_solver_substeps`): all the solver substeps integrate the same
+    /// forces. The forces read with `RigidBody::user_force` and `RigidBody::user_torque` here are
+    /// the ones that will be integrated during this CCD substep, and they can still be modified.
+    fn on_apply_forces(&self, _bodies: &mut RigidBodySet) {}
 }
 
 impl PhysicsHooks for () {
@@ -1,20 +1,19 @@
 //! Physics pipeline structures.
 
 use crate::counters::Counters;
-#[cfg(not(feature = "parallel"))]
-use crate::dynamics::IslandSolver;
 use crate::dynamics::{
-    CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
-    RigidBodyPosition, RigidBodyType,
+    CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, IslandSolver,
+    MultibodyJointSet, RigidBodyHandle, RigidBodyType,
 };
 #[cfg(feature = "parallel")]
-use crate::dynamics::{JointGraphEdge, ParallelIslandSolver as IslandSolver};
+use crate::dynamics::{JointGraphEdge, ParallelIslandSolver};
 use crate::geometry::{
-    BroadPhase, BroadPhasePairEvent, ColliderChanges, ColliderHandle, ColliderPair,
-    ContactManifoldIndex, NarrowPhase,
+    BroadPhase, BroadPhaseAlgorithm, BroadPhasePairEvent, ColliderChanges, ColliderHandle,
+    ColliderPair, ContactManifold, ContactManifoldIndex, NarrowPhase,
 };
-use crate::math::{Real, Vector};
+use crate::math::{Isometry, Point, Real, Vector};
 use crate::pipeline::{EventHandler, PhysicsHooks};
+use std::collections::HashMap;
 use {crate::dynamics::RigidBodySet, crate::geometry::ColliderSet};
 
 /// The physics pipeline, responsible for stepping the whole physics simulation.
@@ -36,6 +35,11 @@ pub struct PhysicsPipeline {
     broadphase_collider_pairs: Vec<ColliderPair>,
     broad_phase_events: Vec<BroadPhasePairEvent>,
     solvers: Vec<IslandSolver>,
+    #[cfg(feature = "parallel")]
+    parallel_solvers: Vec<ParallelIslandSolver>,
+    ccd_impacts: Vec<(RigidBodyHandle, Point<Real>, Vector<Real>)>,
+    #[cfg(feature = "parallel")]
+    thread_pool: Option<rayon::ThreadPool>,
 }
 
 impl Default for PhysicsPipeline {
@@ -56,13 +60,27 @@ impl PhysicsPipeline {
         PhysicsPipeline {
             counters: Counters::new(true),
             solvers: Vec::new(),
+            #[cfg(feature = "parallel")]
+            parallel_solvers: Vec::new(),
             manifold_indices: Vec::new(),
             joint_constraint_indices: Vec::new(),
             broadphase_collider_pairs: Vec::new(),
             broad_phase_events: Vec::new(),
+            ccd_impacts: Vec::new(),
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
         }
     }
 
+    /// The motion-clamping performed by the CCD solver during the last timestep.
+    ///
+    /// Each element contains the handle of a rigid-body stopped by CCD, the world-space
+    /// point where it hit an obstacle, and the contact normal at this point, pointing
+    /// towards the rigid-body.
+    pub fn last_ccd_impacts(&self) -> &[(RigidBodyHandle, Point<Real>, Vector<Real>)] {
+        &self.ccd_impacts
+    }
+
     fn clear_modified_colliders(
         &mut self,
         colliders: &mut ColliderSet,
@@ -79,12 +97,14 @@ impl PhysicsPipeline {
         &mut self,
         integration_parameters: &IntegrationParameters,
         islands: &mut IslandManager,
-        broad_phase: &mut BroadPhase,
+        broad_phase: &mut dyn BroadPhaseAlgorithm,
         narrow_phase: &mut NarrowPhase,
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
         modified_colliders: &[ColliderHandle],
         removed_colliders: &[ColliderHandle],
+        substep: usize,
+        remaining_time: Real,
         hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
         handle_user_changes: bool,
@@ -95,8 +115,11 @@ impl PhysicsPipeline {
         // Update broad-phase.
         self.broad_phase_events.clear();
         self.broadphase_collider_pairs.clear();
+        // NOTE: the contact pairs within the contact event hysteresis distance must
+        //       be kept by the broad-phase to avoid early stop events.
         broad_phase.update(
-            integration_parameters.prediction_distance,
+            integration_parameters.prediction_distance
+                + integration_parameters.contact_event_hysteresis,
             colliders,
             modified_colliders,
             removed_colliders,
@@ -124,15 +147,34 @@ impl PhysicsPipeline {
             &self.broad_phase_events,
             events,
         );
+
+        self.counters.cd.contacts_time.resume();
         narrow_phase.compute_contacts(
             integration_parameters.prediction_distance,
+            integration_parameters.contact_event_hysteresis,
             bodies,
             colliders,
             modified_colliders,
+            substep,
+            remaining_time,
             hooks,
             events,
         );
-        narrow_phase.compute_intersections(bodies, colliders, modified_colliders, hooks, events);
+        self.counters.cd.contacts_time.pause();
+
+        if integration_parameters.compute_intersections {
+            self.counters.cd.intersections_time.resume();
+            narrow_phase.compute_intersections(
+                bodies,
+                colliders,
+                modified_colliders,
+                substep,
+                remaining_time,
+                hooks,
+                events,
+            );
+            self.counters.cd.intersections_time.pause();
+        }
 
         self.counters.cd.narrow_phase_time.pause();
         self.counters.stages.collision_detection_time.pause();
@@ -148,6 +190,10 @@ impl PhysicsPipeline {
         colliders: &mut ColliderSet,
         impulse_joints: &mut ImpulseJointSet,
         multibody_joints: &mut MultibodyJointSet,
+        modified_colliders: &mut Vec<ColliderHandle>,
+        first_substep: &mut bool,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
     ) {
         self.counters.stages.island_construction_time.resume();
         islands.update_active_set_with_contacts(
@@ -158,6 +204,7 @@ impl PhysicsPipeline {
             impulse_joints,
             multibody_joints,
             integration_parameters.min_island_size,
+            events,
         );
         self.counters.stages.island_construction_time.pause();
 
@@ -184,7 +231,15 @@ impl PhysicsPipeline {
             &mut self.joint_constraint_indices,
         );
 
+        // Split this (CCD) substep into solver substeps sharing the same islands,
+        // contact manifolds, and external forces.
+        let num_solver_substeps = integration_parameters.num_solver_substeps.max(1);
+        let mut solver_parameters = *integration_parameters;
+        solver_parameters.dt /= num_solver_substeps as Real;
+
         self.counters.stages.update_time.resume();
+        hooks.on_apply_forces(bodies);
+
         for handle in islands.active_dynamic_bodies() {
             let rb = bodies.index_mut_internal(*handle);
             rb.mprops.update_world_mass_properties(&rb.pos.position);
@@ -194,21 +249,81 @@ impl PhysicsPipeline {
         }
 
         for multibody in &mut multibody_joints.multibodies {
-            multibody
-                .1
-                .update_dynamics(integration_parameters.dt, bodies);
+            multibody.1.update_dynamics(solver_parameters.dt, bodies);
             multibody.1.update_acceleration(bodies);
         }
         self.counters.stages.update_time.pause();
 
-        self.counters.stages.solver_time.resume();
-        if self.solvers.len() < islands.num_islands() {
-            self.solvers
-                .resize_with(islands.num_islands(), IslandSolver::new);
+        for solver_substep in 0..num_solver_substeps {
+            if solver_substep > 0 {
+                // The kinematic bodies move by one solver substep out of the
+                // `num_solver_substeps - solver_substep + 1` they have left.
+                let kinematic_fraction = 1.0 / (num_solver_substeps - solver_substep + 1) as Real;
+                self.advance_to_intermediate_positions(
+                    islands,
+                    &mut manifolds,
+                    bodies,
+                    colliders,
+                    modified_colliders,
+                    *first_substep,
+                    kinematic_fraction,
+                );
+                *first_substep = false;
+            }
+
+            if !solver_parameters.warmstart {
+                // Discard the impulses cached during the previous (sub)step.
+                for manifold in manifolds.iter_mut() {
+                    for contact in manifold.points.iter_mut() {
+                        contact.data.impulse = 0.0;
+                        contact.data.tangent_impulse = na::zero();
+                    }
+                }
+
+                let joints = impulse_joints.joints_mut();
+                for joint_indices in &self.joint_constraint_indices[..islands.num_islands()] {
+                    for joint_id in joint_indices {
+                        let joint = &mut joints[*joint_id].weight;
+                        joint.impulses = na::zero();
+
+                        for limit in joint.data.limits.iter_mut() {
+                            limit.impulse = 0.0;
+                        }
+
+                        for motor in joint.data.motors.iter_mut() {
+                            motor.impulse = 0.0;
+                        }
+                    }
+                }
+            }
+
+            self.solve_velocity_constraints(
+                &solver_parameters,
+                islands,
+                bodies,
+                &mut manifolds,
+                impulse_joints,
+                multibody_joints,
+            );
         }
+    }
+
+    fn solve_velocity_constraints(
+        &mut self,
+        integration_parameters: &IntegrationParameters,
+        islands: &IslandManager,
+        bodies: &mut RigidBodySet,
+        manifolds: &mut Vec<&mut ContactManifold>,
+        impulse_joints: &mut ImpulseJointSet,
+        multibody_joints: &mut MultibodyJointSet,
+    ) {
+        self.counters.stages.solver_time.resume();
+        if !cfg!(feature = "parallel") || integration_parameters.force_serial_solver {
+            if self.solvers.len() < islands.num_islands() {
+                self.solvers
+                    .resize_with(islands.num_islands(), IslandSolver::new);
+            }
 
-        #[cfg(not(feature = "parallel"))]
-        {
             enable_flush_to_zero!();
 
             for island_id in 0..islands.num_islands() {
@@ -225,55 +340,91 @@ impl PhysicsPipeline {
                     multibody_joints,
                 )
             }
-        }
+        } else {
+            #[cfg(feature = "parallel")]
+            {
+                use crate::geometry::ContactManifold;
+                use rayon::prelude::*;
+                use std::sync::atomic::Ordering;
+
+                match integration_parameters.num_threads.filter(|n| *n > 0) {
+                    Some(num_threads)
+                        if self
+                            .thread_pool
+                            .as_ref()
+                            .map(|pool| pool.current_num_threads())
+                            != Some(num_threads) =>
+                    {
+                        self.thread_pool = Some(
+                            rayon::ThreadPoolBuilder::new()
+                                .num_threads(num_threads)
+                                .build()
+                                .expect("Failed to create the solver thread pool."),
+                        );
+                    }
+                    Some(_) => {}
+                    None => self.thread_pool = None,
+                }
+
+                let num_islands = islands.num_islands();
+                if self.parallel_solvers.len() < num_islands {
+                    self.parallel_solvers
+                        .resize_with(num_islands, ParallelIslandSolver::new);
+                }
 
-        #[cfg(feature = "parallel")]
-        {
-            use crate::geometry::ContactManifold;
-            use rayon::prelude::*;
-            use std::sync::atomic::Ordering;
-
-            let num_islands = islands.num_islands();
-            let solvers = &mut self.solvers[..num_islands];
-            let bodies = &std::sync::atomic::AtomicPtr::new(bodies as *mut _);
-            let manifolds = &std::sync::atomic::AtomicPtr::new(&mut manifolds as *mut _);
-            let impulse_joints =
-                &std::sync::atomic::AtomicPtr::new(impulse_joints.joints_vec_mut() as *mut _);
-            let multibody_joints = &std::sync::atomic::AtomicPtr::new(multibody_joints as *mut _);
-            let manifold_indices = &self.manifold_indices[..];
-            let joint_constraint_indices = &self.joint_constraint_indices[..];
-
-            rayon::scope(|scope| {
-                enable_flush_to_zero!();
-
-                solvers
-                    .par_iter_mut()
-                    .enumerate()
-                    .for_each(|(island_id, solver)| {
-                        let bodies: &mut RigidBodySet =
-                            unsafe { std::mem::transmute(bodies.load(Ordering::Relaxed)) };
-                        let manifolds: &mut Vec<&mut ContactManifold> =
-                            unsafe { std::mem::transmute(manifolds.load(Ordering::Relaxed)) };
-                        let impulse_joints: &mut Vec<JointGraphEdge> =
-                            unsafe { std::mem::transmute(impulse_joints.load(Ordering::Relaxed)) };
-                        let multibody_joints: &mut MultibodyJointSet = unsafe {
-                            std::mem::transmute(multibody_joints.load(Ordering::Relaxed))
-                        };
-
-                        solver.init_and_solve(
-                            scope,
-                            island_id,
-                            islands,
-                            integration_parameters,
-                            bodies,
-                            manifolds,
-                            &manifold_indices[island_id],
-                            impulse_joints,
-                            &joint_constraint_indices[island_id],
-                            multibody_joints,
-                        )
+                let solvers = &mut self.parallel_solvers[..num_islands];
+                let bodies = &std::sync::atomic::AtomicPtr::new(bodies as *mut _);
+                let manifolds = &std::sync::atomic::AtomicPtr::new(manifolds as *mut _);
+                let impulse_joints =
+                    &std::sync::atomic::AtomicPtr::new(impulse_joints.joints_vec_mut() as *mut _);
+                let multibody_joints =
+                    &std::sync::atomic::AtomicPtr::new(multibody_joints as *mut _);
+                let manifold_indices = &self.manifold_indices[..];
+                let joint_constraint_indices = &self.joint_constraint_indices[..];
+
+                // NOTE: the islands don't share any mutable state, so the order in which they
+                //       are solved doesn't affect the results.
+                let solve = || {
+                    rayon::scope(|scope| {
+                        enable_flush_to_zero!();
+
+                        solvers
+                            .par_iter_mut()
+                            .enumerate()
+                            .for_each(|(island_id, solver)| {
+                                let bodies: &mut RigidBodySet =
+                                    unsafe { std::mem::transmute(bodies.load(Ordering::Relaxed)) };
+                                let manifolds: &mut Vec<&mut ContactManifold> = unsafe {
+                                    std::mem::transmute(manifolds.load(Ordering::Relaxed))
+                                };
+                                let impulse_joints: &mut Vec<JointGraphEdge> = unsafe {
+                                    std::mem::transmute(impulse_joints.load(Ordering::Relaxed))
+                                };
+                                let multibody_joints: &mut MultibodyJointSet = unsafe {
+                                    std::mem::transmute(multibody_joints.load(Ordering::Relaxed))
+                                };
+
+                                solver.init_and_solve(
+                                    scope,
+                                    island_id,
+                                    islands,
+                                    integration_parameters,
+                                    bodies,
+                                    manifolds,
+                                    &manifold_indices[island_id],
+                                    impulse_joints,
+                                    &joint_constraint_indices[island_id],
+                                    multibody_joints,
+                                )
+                            });
                     });
-            });
+                };
+
+                match &self.thread_pool {
+                    Some(pool) => pool.install(solve),
+                    None => solve(),
+                }
+            }
         }
         self.counters.stages.solver_time.pause();
     }
@@ -286,6 +437,7 @@ impl PhysicsPipeline {
         colliders: &mut ColliderSet,
         narrow_phase: &NarrowPhase,
         ccd_solver: &mut CCDSolver,
+        hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
         self.counters.ccd.toi_computation_time.start();
@@ -298,7 +450,11 @@ impl PhysicsPipeline {
             narrow_phase,
             events,
         );
-        ccd_solver.clamp_motions(integration_parameters.dt, bodies, &impacts);
+        let clamped = ccd_solver.clamp_motions(integration_parameters.dt, bodies, &impacts);
+        if !clamped.is_empty() {
+            hooks.on_ccd_motion_clamped(integration_parameters.dt, bodies, &clamped);
+        }
+        self.ccd_impacts.extend(clamped);
         self.counters.ccd.toi_computation_time.pause();
     }
 
@@ -308,16 +464,107 @@ impl PhysicsPipeline {
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
         modified_colliders: &mut Vec<ColliderHandle>,
+        first_substep: bool,
     ) {
         // Set the rigid-bodies and kinematic bodies to their final position.
         for handle in islands.iter_active_bodies() {
             let rb = bodies.index_mut_internal(handle);
+            if first_substep {
+                rb.pos.previous_position = rb.pos.position;
+            }
             rb.pos.position = rb.pos.next_position;
             rb.colliders
                 .update_positions(colliders, modified_colliders, &rb.pos.position);
         }
     }
 
+    fn advance_to_intermediate_positions(
+        &mut self,
+        islands: &IslandManager,
+        manifolds: &mut [&mut ContactManifold],
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        modified_colliders: &mut Vec<ColliderHandle>,
+        first_substep: bool,
+        kinematic_fraction: Real,
+    ) {
+        // Move the solver contacts before the bodies, because this needs both
+        // their current and next positions.
+        let displacement = |handle: Option<RigidBodyHandle>, pt: &Point<Real>| {
+            handle
+                .and_then(|h| bodies.get(h))
+                .map(|rb| {
+                    let target = if rb.is_dynamic() {
+                        rb.pos.next_position
+                    } else {
+                        rb.pos
+                            .position
+                            .lerp_slerp(&rb.pos.next_position, kinematic_fraction)
+                    };
+                    target * rb.pos.position.inverse_transform_point(pt) - pt
+                })
+                .unwrap_or_else(Vector::zeros)
+        };
+
+        for manifold in manifolds.iter_mut() {
+            let normal = manifold.data.normal;
+            let (rb1, rb2) = (manifold.data.rigid_body1, manifold.data.rigid_body2);
+
+            for contact in &mut manifold.data.solver_contacts {
+                let disp1 = displacement(rb1, &contact.point);
+                let disp2 = displacement(rb2, &contact.point);
+                contact.dist += (disp2 - disp1).dot(&normal);
+                contact.point += (disp1 + disp2) * 0.5;
+                contact.is_new = false;
+            }
+        }
+
+        // The dynamic bodies move to the position computed by the solver. The kinematic
+        // bodies only cover `kinematic_fraction` of their remaining motion: they will reach
+        // their final position in `advance_to_final_positions`.
+        for handle in islands.iter_active_bodies() {
+            let rb = bodies.index_mut_internal(handle);
+            if first_substep {
+                rb.pos.previous_position = rb.pos.position;
+            }
+
+            if rb.is_dynamic() {
+                rb.pos.position = rb.pos.next_position;
+            } else {
+                rb.pos.position = rb
+                    .pos
+                    .position
+                    .lerp_slerp(&rb.pos.next_position, kinematic_fraction);
+            }
+
+            rb.mprops.update_world_mass_properties(&rb.pos.position);
+
+            rb.colliders
+                .update_positions(colliders, modified_colliders, &rb.pos.position);
+        }
+    }
+
+    fn report_invalid_bodies(
+        &self,
+        islands: &IslandManager,
+        bodies: &RigidBodySet,
+        events: &dyn EventHandler,
+    ) {
+        for handle in islands.iter_active_bodies() {
+            let rb = &bodies[handle];
+            let position_is_finite = rb
+                .pos
+                .position
+                .to_homogeneous()
+                .iter()
+                .all(|x| x.is_finite());
+
+            if !position_is_finite || !rb.vels.is_finite() {
+                events.handle_invalid_body(bodies, handle);
+            }
+        }
+    }
+
     fn interpolate_kinematic_velocities(
         &mut self,
         integration_parameters: &IntegrationParameters,
@@ -340,12 +587,14 @@ impl PhysicsPipeline {
                     );
                 }
                 RigidBodyType::KinematicVelocityBased => {
-                    let new_pos = rb.vels.integrate(
+                    // Don't teleport the body: like the position-based kinematic bodies,
+                    // it reaches its next position at the end of this substep, so its
+                    // previous position is kept for interpolation.
+                    rb.pos.next_position = rb.vels.integrate(
                         integration_parameters.dt,
                         &rb.pos.position,
                         &rb.mprops.local_mprops.local_com,
                     );
-                    rb.pos = RigidBodyPosition::from(new_pos);
                 }
                 _ => {}
             }
@@ -361,7 +610,7 @@ impl PhysicsPipeline {
         gravity: &Vector<Real>,
         integration_parameters: &IntegrationParameters,
         islands: &mut IslandManager,
-        broad_phase: &mut BroadPhase,
+        broad_phase: &mut dyn BroadPhaseAlgorithm,
         narrow_phase: &mut NarrowPhase,
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
@@ -371,12 +620,15 @@ impl PhysicsPipeline {
         hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
+        hooks.on_step_begin(bodies, colliders);
+
         let modified_bodies = bodies.take_modified();
         let mut modified_colliders = colliders.take_modified();
         let mut removed_colliders = colliders.take_removed();
 
         self.counters.reset();
         self.counters.step_started();
+        self.ccd_impacts.clear();
 
         super::user_changes::handle_user_changes_to_colliders(
             bodies,
@@ -409,6 +661,8 @@ impl PhysicsPipeline {
             colliders,
             &modified_colliders[..],
             &mut removed_colliders,
+            0,
+            integration_parameters.dt,
             hooks,
             events,
             true,
@@ -427,6 +681,9 @@ impl PhysicsPipeline {
                 (true, integration_parameters.max_ccd_substeps)
             };
 
+        let mut first_substep = true;
+        let mut num_substeps = 0;
+
         while remaining_substeps > 0 {
             // If there are more than one CCD substep, we need to split
             // the timestep into multiple intervals. First, estimate the
@@ -441,8 +698,14 @@ impl PhysicsPipeline {
             if ccd_is_enabled && remaining_substeps > 1 {
                 // NOTE: Take forces into account when updating the bodies CCD activation flags
                 //       these forces have not been integrated to the body's velocity yet.
-                let ccd_active =
+                let mut ccd_active =
                     ccd_solver.update_ccd_active_flags(islands, bodies, remaining_time, true);
+                if let Some(max_active) = integration_parameters.max_ccd_substepping_bodies {
+                    if ccd_active {
+                        ccd_active =
+                            ccd_solver.limit_ccd_active_bodies(islands, bodies, max_active);
+                    }
+                }
                 let first_impact = if ccd_active {
                     ccd_solver.find_first_impact(
                         remaining_time,
@@ -486,8 +749,10 @@ impl PhysicsPipeline {
             }
 
             self.counters.ccd.num_substeps += 1;
+            num_substeps += 1;
 
             self.interpolate_kinematic_velocities(&integration_parameters, islands, bodies);
+
             self.build_islands_and_solve_velocity_constraints(
                 gravity,
                 &integration_parameters,
@@ -497,8 +762,16 @@ impl PhysicsPipeline {
                 colliders,
                 impulse_joints,
                 multibody_joints,
+                &mut modified_colliders,
+                &mut first_substep,
+                hooks,
+                events,
             );
 
+            // The bodies are now at the beginning of the last solver substep, so the CCD
+            // only has to check the motion of that last solver substep.
+            integration_parameters.dt /= integration_parameters.num_solver_substeps.max(1) as Real;
+
             // If CCD is enabled, execute the CCD motion clamping.
             if ccd_is_enabled {
                 // NOTE: don't the forces into account when updating the CCD active flags because
@@ -517,12 +790,24 @@ impl PhysicsPipeline {
                         colliders,
                         narrow_phase,
                         ccd_solver,
+                        hooks,
                         events,
                     );
                 }
             }
 
-            self.advance_to_final_positions(islands, bodies, colliders, &mut modified_colliders);
+            self.advance_to_final_positions(
+                islands,
+                bodies,
+                colliders,
+                &mut modified_colliders,
+                first_substep,
+            );
+            first_substep = false;
+
+            if integration_parameters.validate {
+                self.report_invalid_bodies(islands, bodies, events);
+            }
 
             self.detect_collisions(
                 &integration_parameters,
@@ -533,6 +818,8 @@ impl PhysicsPipeline {
                 colliders,
                 &mut modified_colliders,
                 &mut removed_colliders,
+                num_substeps,
+                remaining_time,
                 hooks,
                 events,
                 false,
@@ -552,19 +839,170 @@ impl PhysicsPipeline {
             rb.mprops.update_world_mass_properties(&rb.pos.position);
         }
 
+        // Keep track of the bodies that moved, so users can synchronize them.
+        bodies.moved_bodies.clear();
+        for handle in islands.iter_active_bodies() {
+            let rb = &bodies[handle];
+            if rb.pos.position != rb.pos.previous_position {
+                bodies.moved_bodies.push(handle);
+            }
+        }
+
         self.counters.step_completed();
     }
+
+    /// Advances the simulation of the given rigid-bodies only, by one timestep.
+    ///
+    /// The given rigid-bodies, their colliders, and the impulse joints attached between two of
+    /// them are simulated as if they were the only elements of the world: they only interact with
+    /// each other, and all the other rigid-bodies and colliders are left untouched. In particular,
+    /// a fixed rigid-body (e.g. the ground) is only taken into account if it is part of
+    /// `body_handles`, and the colliders without parent are always ignored. The multibody joints
+    /// are ignored too.
+    ///
+    /// This is done by simulating copies of these elements with their own broad-phase and
+    /// narrow-phase, and writing the resulting positions and velocities back into `bodies` and
+    /// `colliders`. So, unlike `self.step`, the contacts are computed from scratch at each call
+    /// and don't benefit from warmstarting. No event is generated, no physics hook is called,
+    /// and the sleeping state of the rigid-bodies is not modified.
+    ///
+    /// The simulation runs on a temporary pipeline, so the state of `self` isn't shared with it:
+    /// `self.counters`, `self.last_ccd_impacts()`, and the solver buffers still reflect the
+    /// last call to `self.step`. Only `bodies` and `colliders` are modified.
+    pub fn step_subset(
+        &mut self,
+        body_handles: &[RigidBodyHandle],
+        gravity: &Vector<Real>,
+        integration_parameters: &IntegrationParameters,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        impulse_joints: &ImpulseJointSet,
+    ) {
+        let mut subset_bodies = RigidBodySet::new();
+        let mut subset_colliders = ColliderSet::new();
+        let mut subset_joints = ImpulseJointSet::new();
+        let mut subset_handles = HashMap::new();
+
+        for handle in body_handles {
+            if subset_handles.contains_key(handle) {
+                continue;
+            }
+
+            if let Some(rb) = bodies.get(*handle) {
+                let subset_handle = subset_bodies.insert(rb.clone());
+
+                for co_handle in rb.colliders() {
+                    subset_colliders.insert_with_parent(
+                        colliders[*co_handle].clone(),
+                        subset_handle,
+                        &mut subset_bodies,
+                    );
+                }
+
+                // Attaching the colliders added their mass properties on top of
+                // the ones already cloned with the rigid-body.
+                subset_bodies.index_mut_internal(subset_handle).mprops = rb.mprops.clone();
+                subset_handles.insert(*handle, subset_handle);
+            }
+        }
+
+        for (_, joint) in impulse_joints.iter() {
+            if let (Some(body1), Some(body2)) = (
+                subset_handles.get(&joint.body1),
+                subset_handles.get(&joint.body2),
+            ) {
+                subset_joints.insert(*body1, *body2, joint.data, false);
+            }
+        }
+
+        PhysicsPipeline::new().step(
+            gravity,
+            integration_parameters,
+            &mut IslandManager::new(),
+            &mut BroadPhase::new(),
+            &mut NarrowPhase::new(),
+            &mut subset_bodies,
+            &mut subset_colliders,
+            &mut subset_joints,
+            &mut MultibodyJointSet::new(),
+            &mut CCDSolver::new(),
+            &(),
+            &(),
+        );
+
+        for (handle, subset_handle) in subset_handles {
+            let subset_rb = &subset_bodies[subset_handle];
+            let rb = bodies.index_mut_internal(handle);
+            rb.pos = subset_rb.pos;
+            rb.vels = subset_rb.vels;
+            rb.integrated_vels = subset_rb.integrated_vels;
+
+            for co_handle in rb.colliders() {
+                if let Some(co) = colliders.get_mut(*co_handle) {
+                    let new_pos = rb.pos.position * co.position_wrt_parent().unwrap();
+                    co.set_position(new_pos);
+                }
+            }
+        }
+    }
+
+    /// Predicts the positions of the dynamic rigid-bodies over `num_steps` timesteps, integrating
+    /// them under gravity while ignoring all collisions and joints.
+    ///
+    /// The rigid-bodies are not modified. For each enabled and unfrozen dynamic rigid-body, this returns
+    /// its position at the end of each one of these timesteps, e.g., for drawing the predicted
+    /// trajectory of a projectile. The user forces, gravity scale, and damping of each rigid-body
+    /// are taken into account.
+    pub fn integrate_only(
+        gravity: &Vector<Real>,
+        integration_parameters: &IntegrationParameters,
+        bodies: &RigidBodySet,
+        num_steps: usize,
+    ) -> Vec<(RigidBodyHandle, Vec<Isometry<Real>>)> {
+        let dt = integration_parameters.dt;
+
+        bodies
+            .iter()
+            .filter(|(_, rb)| rb.is_dynamic() && rb.is_enabled() && !rb.is_frozen())
+            .map(|(handle, rb)| {
+                let mut position = rb.pos.position;
+                let mut vels = rb.vels;
+                let mut mprops = rb.mprops.clone();
+                let mut forces = rb.forces;
+                let mut samples = Vec::with_capacity(num_steps);
+
+                for _ in 0..num_steps {
+                    mprops.update_world_mass_properties(&position);
+                    forces.compute_effective_force_and_torque(gravity, &mprops.effective_mass());
+                    vels = forces
+                        .integrate(dt, &vels, &mprops)
+                        .apply_damping(dt, &rb.damping);
+                    position = vels.integrate(dt, &position, &mprops.local_mprops.local_com);
+                    samples.push(position);
+                }
+
+                (handle, samples)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::dynamics::{
-        CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, RigidBodyBuilder,
-        RigidBodySet,
+        CCDSolver, GenericJointBuilder, ImpulseJointSet, IntegrationParameters, IslandManager,
+        JointAxesMask, JointAxis, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
+        SpringJointBuilder,
+    };
+    use crate::geometry::{
+        BroadPhase, ColliderBuilder, ColliderHandle, ColliderSet, InteractionGroups, NarrowPhase,
+    };
+    use crate::math::{Point, Real, Vector};
+    use crate::pipeline::test_world::TestWorld;
+    use crate::pipeline::{
+        ActiveEvents, ActiveHooks, CollectorEventHandler, ContactModificationContext, PhysicsHooks,
+        PhysicsPipeline,
     };
-    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
-    use crate::math::Vector;
-    use crate::pipeline::PhysicsPipeline;
     use crate::prelude::MultibodyJointSet;
 
     #[test]
@@ -714,6 +1152,39 @@ mod test {
         assert_eq!(h3a, h3b);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn impulse_joint_removal_snapshot_handle_determinism() {
+        let mut bodies = RigidBodySet::new();
+        let b1 = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let b2 = bodies.insert(RigidBodyBuilder::dynamic().build());
+
+        let mut impulse_joints = ImpulseJointSet::new();
+        let joint = GenericJointBuilder::new(JointAxesMask::LOCKED_FIXED_AXES).build();
+        let h1 = impulse_joints.insert(b1, b2, joint, true);
+        let h2 = impulse_joints.insert(b1, b2, joint, true);
+        let h3 = impulse_joints.insert(b1, b2, joint, true);
+
+        impulse_joints.remove(h1, true);
+        impulse_joints.remove(h3, true);
+        impulse_joints.remove(h2, true);
+
+        let ser_joints = bincode::serialize(&impulse_joints).unwrap();
+        let mut impulse_joints2: ImpulseJointSet = bincode::deserialize(&ser_joints).unwrap();
+
+        let h1a = impulse_joints.insert(b1, b2, joint, true);
+        let h2a = impulse_joints.insert(b1, b2, joint, true);
+        let h3a = impulse_joints.insert(b1, b2, joint, true);
+
+        let h1b = impulse_joints2.insert(b1, b2, joint, true);
+        let h2b = impulse_joints2.insert(b1, b2, joint, true);
+        let h3b = impulse_joints2.insert(b1, b2, joint, true);
+
+        assert_eq!(h1a, h1b);
+        assert_eq!(h2a, h2b);
+        assert_eq!(h3a, h3b);
+    }
+
     #[test]
     fn collider_removal_before_step() {
         let mut pipeline = PhysicsPipeline::new();
@@ -761,4 +1232,2065 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn constant_force_is_independent_of_ccd_substeps() {
+        fn run(max_ccd_substeps: usize) -> (Vector<Real>, Real, usize) {
+            let mut world = TestWorld::new();
+            world.integration_parameters.max_ccd_substeps = max_ccd_substeps;
+
+            // The body subject to a constant force, far away from everything else.
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(-Vector::x() * 100.0)
+                .ccd_enabled(true)
+                .build();
+            let pushed = world.bodies.insert(rb);
+            let co = ColliderBuilder::ball(0.5).build();
+            world
+                .colliders
+                .insert_with_parent(co, pushed, &mut world.bodies);
+            world.bodies[pushed].add_force(Vector::x() * 10.0, true);
+
+            // A fast body hitting a wall, to trigger CCD substepping.
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(Vector::x() * 100.0)
+                .linvel(Vector::x() * 200.0)
+                .ccd_enabled(true)
+                .build();
+            let bullet = world.bodies.insert(rb);
+            let co = ColliderBuilder::ball(0.1).build();
+            world
+                .colliders
+                .insert_with_parent(co, bullet, &mut world.bodies);
+
+            let rb = RigidBodyBuilder::fixed()
+                .translation(Vector::x() * 102.0)
+                .build();
+            let wall = world.bodies.insert(rb);
+            let co = ColliderBuilder::ball(0.1).build();
+            world
+                .colliders
+                .insert_with_parent(co, wall, &mut world.bodies);
+
+            world.step(&Vector::zeros());
+
+            (
+                *world.bodies[pushed].linvel(),
+                world.bodies[pushed].mass(),
+                world.pipeline.counters.ccd.num_substeps,
+            )
+        }
+
+        let (linvel1, mass, num_substeps1) = run(1);
+        let (linvel4, _, num_substeps4) = run(4);
+        let expected = Vector::x() * 10.0 / mass * IntegrationParameters::default().dt;
+
+        assert_eq!(num_substeps1, 1);
+        assert!(num_substeps4 > 1);
+        assert!((linvel1 - expected).norm() < 1.0e-5);
+        assert!((linvel4 - expected).norm() < 1.0e-5);
+    }
+
+    #[test]
+    fn joint_limit_restitution_makes_a_pendulum_rebound() {
+        // Returns the angular velocity of the pendulum right before and right after it hits
+        // its limit.
+        fn run(restitution: Real) -> (Real, Real) {
+            let mut world = TestWorld::new();
+            let gravity = Vector::y() * -9.81;
+
+            // A horizontal pendulum falling down, with a limit stopping it once it hangs
+            // vertically, after a rotation of 90 degrees.
+            #[cfg(feature = "dim2")]
+            let arm = Vector::x();
+            #[cfg(feature = "dim3")]
+            let arm = Vector::z();
+            let limit = std::f64::consts::FRAC_PI_2 as Real;
+
+            let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+            let body = world
+                .bodies
+                .insert(RigidBodyBuilder::dynamic().translation(arm).build());
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1).build(),
+                body,
+                &mut world.bodies,
+            );
+            let joint = GenericJointBuilder::new(JointAxesMask::LOCKED_REVOLUTE_AXES)
+                .local_anchor2(Point::origin() - arm)
+                .limits(JointAxis::AngX, [-limit, limit])
+                .limit_restitution(JointAxis::AngX, restitution);
+            world.impulse_joints.insert(ground, body, joint, true);
+
+            let mut prev_angvel: Real = 0.0;
+
+            for _ in 0..120 {
+                world.step(&gravity);
+
+                #[cfg(feature = "dim2")]
+                let angvel = world.bodies[body].angvel();
+                #[cfg(feature = "dim3")]
+                let angvel = world.bodies[body].angvel().x;
+
+                // The pendulum only slows down when it hits the limit.
+                if prev_angvel.abs() > 1.0
+                    && (angvel * prev_angvel <= 0.0 || angvel.abs() < prev_angvel.abs() * 0.8)
+                {
+                    return (prev_angvel, angvel);
+                }
+
+                prev_angvel = angvel;
+            }
+
+            panic!("The pendulum never hit its limit.");
+        }
+
+        // Without restitution, the pendulum stops at the limit.
+        let (before, after) = run(0.0);
+        assert!((after / before).abs() < 0.1);
+        // Otherwise, it rebounds with a fraction of its velocity.
+        let (before, after) = run(0.5);
+        assert!((after / before + 0.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn joint_limit_restitution_ignores_resting_contacts() {
+        let mut world = TestWorld::new();
+        let gravity = Vector::x() * -9.81;
+
+        // A fully bouncy body resting on the lower limit of a slider, pushed against
+        // it by gravity.
+        let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+        let body = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::x() * -1.0)
+                .can_sleep(false)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            body,
+            &mut world.bodies,
+        );
+        let joint = GenericJointBuilder::new(JointAxesMask::LOCKED_PRISMATIC_AXES)
+            .limits(JointAxis::X, [-1.0, 1.0])
+            .limit_restitution(JointAxis::X, 1.0);
+        world.impulse_joints.insert(ground, body, joint, true);
+
+        for i in 0..120 {
+            world.step(&gravity);
+
+            // The velocity gained from gravity during one step must not be reflected.
+            if i > 5 {
+                assert!(world.bodies[body].linvel().x.abs() < 1.0e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn motor_max_force_stalls_under_heavy_load() {
+        fn run(max_force: Real) -> Real {
+            let mut world = TestWorld::new();
+            let gravity = Vector::x() * -9.81;
+
+            // A motor trying to lift a body along the X axis, against gravity.
+            let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+            let body = world.bodies.insert(RigidBodyBuilder::dynamic().build());
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5).build(),
+                body,
+                &mut world.bodies,
+            );
+            let weight = world.bodies[body].mass() * 9.81;
+            let joint = GenericJointBuilder::new(JointAxesMask::LOCKED_PRISMATIC_AXES)
+                .motor_velocity(JointAxis::X, 1.0, 1000.0)
+                .motor_max_force(JointAxis::X, max_force * weight);
+            world.impulse_joints.insert(ground, body, joint, true);
+
+            for _ in 0..60 {
+                world.step(&gravity);
+            }
+
+            world.bodies[body].translation().x
+        }
+
+        // A motor stronger than the weight lifts the body.
+        assert!(run(10.0) > 0.5);
+        // A motor weaker than the weight can't prevent it from falling.
+        assert!(run(0.5) < -0.5);
+    }
+
+    #[test]
+    fn spring_joint_settles_at_stretched_rest_length() {
+        let mut world = TestWorld::new();
+        let gravity = Vector::y() * -9.81;
+        let (rest_length, stiffness) = (1.0, 100.0);
+
+        // A body hanging from a spring attached to a fixed body.
+        let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+        let body = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * -rest_length)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            body,
+            &mut world.bodies,
+        );
+        let joint = SpringJointBuilder::new(rest_length, stiffness, 10.0);
+        world.impulse_joints.insert(ground, body, joint, true);
+
+        for _ in 0..300 {
+            world.step(&gravity);
+        }
+
+        // At rest, the spring force compensates the weight.
+        let expected_length = rest_length + world.bodies[body].mass() * 9.81 / stiffness;
+        let length = world.bodies[body].translation().norm();
+        assert!((length - expected_length).abs() < 0.05);
+    }
+
+    #[test]
+    fn disabled_rigid_body_is_paused() {
+        let mut world = TestWorld::new();
+        let gravity = Vector::y() * -9.81;
+
+        let handle = world.bodies.insert(RigidBodyBuilder::dynamic().build());
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        for _ in 0..10 {
+            world.step(&gravity);
+        }
+
+        world.bodies.set_enabled(handle, false);
+        let translation = *world.bodies[handle].translation();
+        let linvel = *world.bodies[handle].linvel();
+
+        for _ in 0..10 {
+            world.step(&gravity);
+        }
+
+        assert_eq!(*world.bodies[handle].translation(), translation);
+        assert_eq!(*world.bodies[handle].linvel(), linvel);
+
+        world.bodies.set_enabled(handle, true);
+        world.step(&gravity);
+
+        assert!(world.bodies[handle].translation().y < translation.y);
+        assert!(world.bodies[handle].linvel().y < linvel.y);
+    }
+
+    #[test]
+    fn velocity_snapping_settles_to_zero() {
+        let mut world = TestWorld::new();
+        world.integration_parameters.velocity_snap_threshold = 1.0e-2;
+        let gravity = Vector::y() * -9.81;
+
+        #[cfg(feature = "dim2")]
+        let (ground_shape, box_shape) = (
+            ColliderBuilder::cuboid(10.0, 0.1),
+            ColliderBuilder::cuboid(0.5, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (ground_shape, box_shape) = (
+            ColliderBuilder::cuboid(10.0, 0.1, 10.0),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+
+        world.colliders.insert(ground_shape.build());
+
+        // A box resting on the ground. It is not allowed to sleep so its velocity can only
+        // reach zero because of the velocity snapping.
+        let rb = RigidBodyBuilder::dynamic()
+            .translation(Vector::y() * 0.61)
+            .can_sleep(false)
+            .build();
+        let handle = world.bodies.insert(rb);
+        world
+            .colliders
+            .insert_with_parent(box_shape.build(), handle, &mut world.bodies);
+
+        // A slow free-flying box, far from the ground, which must not be snapped.
+        let drifting = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 10.0)
+                .linvel(Vector::x() * 5.0e-3)
+                .gravity_scale(0.0)
+                .can_sleep(false)
+                .build(),
+        );
+        world
+            .colliders
+            .insert_with_parent(box_shape.build(), drifting, &mut world.bodies);
+
+        for _ in 0..120 {
+            world.step(&gravity);
+        }
+
+        assert!(!world.bodies[handle].is_sleeping());
+        assert!(world.bodies[handle].vels.is_zero());
+        assert!((world.bodies[drifting].linvel().x - 5.0e-3).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn sensor_toggling_switches_events() {
+        let mut world = TestWorld::new();
+        let events = CollectorEventHandler::new();
+
+        let trap = world.colliders.insert(
+            ColliderBuilder::ball(0.5)
+                .sensor(true)
+                .active_events(ActiveEvents::COLLISION_EVENTS)
+                .build(),
+        );
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::x() * 0.9)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5)
+                .active_events(ActiveEvents::COLLISION_EVENTS)
+                .build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        world.step_with(&Vector::zeros(), &(), &events);
+        let intersection_events = events.drain_intersection_events();
+        assert_eq!(intersection_events.len(), 1);
+        assert!(intersection_events[0].started());
+        assert!(events.drain_contact_events().is_empty());
+
+        // The trap becomes solid.
+        world.colliders.set_sensor(trap, false);
+        world.step_with(&Vector::zeros(), &(), &events);
+        assert!(events.drain_contact_events().iter().any(|e| e.started()));
+        assert!(events
+            .drain_intersection_events()
+            .iter()
+            .all(|e| e.stopped()));
+
+        world.step_with(&Vector::zeros(), &(), &events);
+        assert!(events.drain_intersection_events().is_empty());
+    }
+
+    #[test]
+    fn collider_batch_insertion_matches_incremental_insertion() {
+        let run = |batch: bool| {
+            let mut world = TestWorld::new();
+
+            // Free a slot so the handle allocation order is exercised.
+            let removed = world.colliders.insert(ColliderBuilder::ball(0.5).build());
+            world
+                .colliders
+                .remove(removed, &mut world.islands, &mut world.bodies, false);
+
+            let to_insert: Vec<_> = (0..10)
+                .map(|i| {
+                    let body = world.bodies.insert(
+                        RigidBodyBuilder::dynamic()
+                            .translation(Vector::x() * i as Real * 0.9)
+                            .build(),
+                    );
+                    (ColliderBuilder::ball(0.5).build(), body)
+                })
+                .collect();
+
+            let handles = if batch {
+                world.colliders.insert_batch(to_insert, &mut world.bodies)
+            } else {
+                to_insert
+                    .into_iter()
+                    .map(|(co, body)| {
+                        world
+                            .colliders
+                            .insert_with_parent(co, body, &mut world.bodies)
+                    })
+                    .collect()
+            };
+
+            world.step(&Vector::zeros());
+
+            let mut pairs: Vec<_> = world
+                .narrow_phase
+                .contact_pairs()
+                .map(|pair| (pair.collider1, pair.collider2))
+                .collect();
+            pairs.sort_by_key(|(co1, co2)| (co1.into_raw_parts(), co2.into_raw_parts()));
+            (handles, pairs)
+        };
+
+        let (incremental_handles, incremental_pairs) = run(false);
+        let (batch_handles, batch_pairs) = run(true);
+        assert_eq!(incremental_handles, batch_handles);
+        assert_eq!(incremental_pairs, batch_pairs);
+        assert_eq!(batch_pairs.len(), 9);
+    }
+
+    #[test]
+    fn rigid_body_batch_insertion_matches_incremental_insertion() {
+        let run = |batch: bool| {
+            let mut bodies = RigidBodySet::new();
+            let mut colliders = ColliderSet::new();
+            let mut impulse_joints = ImpulseJointSet::new();
+            let mut multibody_joints = MultibodyJointSet::new();
+            let mut islands = IslandManager::new();
+
+            // Free a few slots so the handle allocation order is exercised.
+            let to_remove: Vec<_> = (0..3)
+                .map(|_| bodies.insert(RigidBodyBuilder::dynamic().build()))
+                .collect();
+            for handle in [to_remove[1], to_remove[0]] {
+                bodies.remove(
+                    handle,
+                    &mut islands,
+                    &mut colliders,
+                    &mut impulse_joints,
+                    &mut multibody_joints,
+                    true,
+                );
+            }
+
+            let to_insert = (0..5).map(|i| {
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * i as Real)
+                    .build()
+            });
+
+            if batch {
+                bodies.insert_batch(to_insert)
+            } else {
+                to_insert.map(|rb| bodies.insert(rb)).collect()
+            }
+        };
+
+        assert_eq!(run(false), run(true));
+    }
+
+    #[test]
+    fn ccd_reports_clamped_impacts() {
+        let mut world = TestWorld::new();
+
+        // A bullet that would tunnel through the obstacle in a single step without CCD.
+        let bullet = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 1000.0)
+                .ccd_enabled(true)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.1).build(),
+            bullet,
+            &mut world.bodies,
+        );
+        let obstacle = world.bodies.insert(
+            RigidBodyBuilder::fixed()
+                .translation(Vector::x() * 10.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(1.0).build(),
+            obstacle,
+            &mut world.bodies,
+        );
+
+        world.step(&Vector::zeros());
+
+        let impacts = world.pipeline.last_ccd_impacts();
+        assert_eq!(impacts.len(), 1);
+        let (handle, point, normal) = impacts[0];
+        assert_eq!(handle, bullet);
+        assert!((point.x - 9.0).abs() < 0.1);
+        assert!(normal.x < -0.9);
+    }
+
+    #[test]
+    fn ccd_motion_clamping_can_be_cancelled_by_hooks() {
+        struct PassThrough;
+        impl PhysicsHooks for PassThrough {
+            fn on_ccd_motion_clamped(
+                &self,
+                dt: Real,
+                bodies: &mut RigidBodySet,
+                clamped: &[(RigidBodyHandle, Point<Real>, Vector<Real>)],
+            ) {
+                for (handle, _, _) in clamped {
+                    bodies[*handle].cancel_ccd_motion_clamping(dt);
+                }
+            }
+        }
+
+        let mut world = TestWorld::new();
+
+        let bullet = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 1000.0)
+                .ccd_enabled(true)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.1).build(),
+            bullet,
+            &mut world.bodies,
+        );
+        let obstacle = world.bodies.insert(
+            RigidBodyBuilder::fixed()
+                .translation(Vector::x() * 10.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(1.0).build(),
+            obstacle,
+            &mut world.bodies,
+        );
+
+        world.step_with(&Vector::zeros(), &PassThrough, &());
+
+        // The impact is still reported, but the bullet went through the obstacle.
+        assert_eq!(world.pipeline.last_ccd_impacts().len(), 1);
+        let expected_x = 1000.0 * world.integration_parameters.dt;
+        assert!((world.bodies[bullet].translation().x - expected_x).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn contact_skin_enlarges_the_contact_prediction() {
+        let has_contact = |skin: Real| {
+            let mut world = TestWorld::new();
+
+            let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+            let co1 = world.colliders.insert_with_parent(
+                ColliderBuilder::ball(1.0).contact_skin(skin).build(),
+                ground,
+                &mut world.bodies,
+            );
+            // Leave a gap of 0.5 between both balls.
+            let ball = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::y() * 2.5)
+                    .build(),
+            );
+            let co2 = world.colliders.insert_with_parent(
+                ColliderBuilder::ball(1.0).build(),
+                ball,
+                &mut world.bodies,
+            );
+
+            world.step(&Vector::zeros());
+
+            world
+                .narrow_phase
+                .contact_pair(co1, co2)
+                .map(|pair| pair.has_any_active_contact)
+                .unwrap_or(false)
+        };
+
+        assert!(!has_contact(0.0));
+        assert!(has_contact(1.0));
+    }
+
+    #[test]
+    fn predict_position_interpolates_the_last_timestep() {
+        let mut world = TestWorld::new();
+
+        let dynamic = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 1.0)
+                .build(),
+        );
+        let kinematic = world.bodies.insert(
+            RigidBodyBuilder::kinematic_velocity_based()
+                .translation(Vector::y() * 10.0)
+                .linvel(Vector::y() * 2.0)
+                .build(),
+        );
+        for handle in [dynamic, kinematic] {
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5).build(),
+                handle,
+                &mut world.bodies,
+            );
+        }
+
+        world.step(&Vector::zeros());
+        let start: Vec<_> = [dynamic, kinematic]
+            .iter()
+            .map(|h| *world.bodies[*h].translation())
+            .collect();
+        world.step(&Vector::zeros());
+
+        for (handle, start) in [dynamic, kinematic].iter().zip(start.iter()) {
+            let end = world.bodies[*handle].translation();
+            assert_ne!(start, end);
+            let halfway = world.bodies[*handle].predict_position(0.5);
+            assert!((halfway.translation.vector - (start + end) * 0.5).norm() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn drain_modified_reports_moving_bodies() {
+        let mut world = TestWorld::new();
+
+        let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(1.0).build(),
+            ground,
+            &mut world.bodies,
+        );
+        let falling = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 10.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(1.0).build(),
+            falling,
+            &mut world.bodies,
+        );
+        let platform = world.bodies.insert(
+            RigidBodyBuilder::kinematic_velocity_based()
+                .translation(Vector::x() * 10.0)
+                .linvel(Vector::x() * 1.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(1.0).build(),
+            platform,
+            &mut world.bodies,
+        );
+
+        world.step(&(Vector::y() * -9.81));
+
+        let mut moved: Vec<_> = world.bodies.drain_modified().collect();
+        moved.sort_by_key(|h| h.into_raw_parts());
+        assert_eq!(moved, vec![falling, platform]);
+        assert_eq!(world.bodies.drain_modified().count(), 0);
+    }
+
+    #[test]
+    fn stacked_bodies_fall_asleep_together() {
+        let mut world = TestWorld::new();
+
+        #[cfg(feature = "dim2")]
+        let (ground_shape, box_shape) = (
+            ColliderBuilder::cuboid(10.0, 0.5),
+            ColliderBuilder::cuboid(0.5, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (ground_shape, box_shape) = (
+            ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+
+        let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+        world
+            .colliders
+            .insert_with_parent(ground_shape.build(), ground, &mut world.bodies);
+
+        let tower: Vec<_> = (0..5)
+            .map(|i| {
+                let handle = world.bodies.insert(
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::y() * (1.0 + i as Real))
+                        .build(),
+                );
+                world
+                    .colliders
+                    .insert_with_parent(box_shape.build(), handle, &mut world.bodies);
+                handle
+            })
+            .collect();
+
+        for _ in 0..1000 {
+            world.step(&(Vector::y() * -9.81));
+
+            let num_sleeping = tower
+                .iter()
+                .filter(|h| world.bodies[**h].is_sleeping())
+                .count();
+            if num_sleeping == tower.len() {
+                return;
+            }
+
+            // The tower must never be partially asleep.
+            assert_eq!(num_sleeping, 0);
+        }
+
+        panic!("The tower never fell asleep.");
+    }
+
+    #[test]
+    fn sleep_events_are_reported_once_per_transition() {
+        struct SleepEvents(std::sync::Mutex<Vec<(RigidBodyHandle, bool)>>);
+        impl crate::pipeline::EventHandler for SleepEvents {
+            fn handle_collision_event(
+                &self,
+                _bodies: &RigidBodySet,
+                _colliders: &ColliderSet,
+                _event: crate::geometry::CollisionEvent,
+                _contact_pair: Option<&crate::geometry::ContactPair>,
+            ) {
+            }
+
+            fn handle_sleep_event(&self, handle: RigidBodyHandle, asleep: bool) {
+                self.0.lock().unwrap().push((handle, asleep));
+            }
+        }
+
+        // The islands are only updated once per timestep, whatever the number of solver substeps.
+        for num_solver_substeps in [1, 4] {
+            let mut world = TestWorld::new();
+            world.integration_parameters.num_solver_substeps = num_solver_substeps;
+            let events = SleepEvents(std::sync::Mutex::new(Vec::new()));
+
+            let handle = world.bodies.insert(RigidBodyBuilder::dynamic().build());
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(1.0).build(),
+                handle,
+                &mut world.bodies,
+            );
+
+            // The body doesn't move, so it falls asleep after a few seconds.
+            for _ in 0..300 {
+                world.step_with(&Vector::zeros(), &(), &events);
+            }
+            assert!(world.bodies[handle].is_sleeping());
+
+            world.bodies[handle].wake_up(true);
+            world.step_with(&Vector::zeros(), &(), &events);
+
+            let events = events.0.into_inner().unwrap();
+            assert_eq!(events, vec![(handle, true), (handle, false)]);
+        }
+    }
+
+    #[test]
+    fn integrate_only_matches_free_fall() {
+        let mut world = TestWorld::new();
+        let gravity = Vector::y() * -9.81;
+
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 5.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        let predicted = PhysicsPipeline::integrate_only(
+            &gravity,
+            &world.integration_parameters,
+            &world.bodies,
+            10,
+        );
+        assert_eq!(predicted.len(), 1);
+        assert_eq!(predicted[0].0, handle);
+        assert_eq!(predicted[0].1.len(), 10);
+        // The bodies must not be modified by the prediction.
+        assert_eq!(*world.bodies[handle].translation(), Vector::zeros());
+
+        for expected in &predicted[0].1 {
+            world.step(&gravity);
+
+            let error = world.bodies[handle].translation() - expected.translation.vector;
+            assert!(error.norm() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn connected_components_ignore_fixed_bodies() {
+        let mut world = TestWorld::new();
+
+        let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+        world.colliders.insert_with_parent(
+            ColliderBuilder::halfspace(Vector::y_axis()).build(),
+            ground,
+            &mut world.bodies,
+        );
+
+        let mut ball = |translation: Vector<Real>| {
+            let handle = world
+                .bodies
+                .insert(RigidBodyBuilder::dynamic().translation(translation).build());
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5).build(),
+                handle,
+                &mut world.bodies,
+            );
+            handle
+        };
+
+        // Two balls resting on the ground, and two balls linked by a joint.
+        let resting1 = ball(Vector::y() * 0.5);
+        let resting2 = ball(Vector::x() * 5.0 + Vector::y() * 0.5);
+        let linked1 = ball(Vector::y() * 10.0);
+        let linked2 = ball(Vector::x() * 2.0 + Vector::y() * 10.0);
+        world.impulse_joints.insert(
+            linked1,
+            linked2,
+            SpringJointBuilder::new(2.0, 1.0, 0.1),
+            true,
+        );
+
+        world.step(&Vector::zeros());
+
+        let mut components = world.bodies.connected_components(
+            &world.colliders,
+            &world.narrow_phase,
+            &world.impulse_joints,
+            &world.multibody_joints,
+        );
+        let normalize = |components: &mut Vec<Vec<RigidBodyHandle>>| {
+            components
+                .iter_mut()
+                .for_each(|c| c.sort_by_key(|h| h.into_raw_parts()));
+            components.sort_by_key(|c| c[0].into_raw_parts());
+        };
+        let mut expected = vec![vec![resting1], vec![resting2], vec![linked1, linked2]];
+        normalize(&mut components);
+        normalize(&mut expected);
+        assert_eq!(components, expected);
+    }
+
+    #[test]
+    fn collision_groups_changes_update_contacts() {
+        let mut world = TestWorld::new();
+        let events = CollectorEventHandler::new();
+
+        world.colliders.insert(
+            ColliderBuilder::halfspace(Vector::y_axis())
+                .active_events(ActiveEvents::COLLISION_EVENTS)
+                .build(),
+        );
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 0.5)
+                .build(),
+        );
+        let ball = world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        world.step_with(&Vector::zeros(), &(), &events);
+        let started = events.drain_contact_events();
+        assert_eq!(started.len(), 1);
+        assert!(started[0].started());
+
+        world
+            .colliders
+            .set_collision_groups(ball, InteractionGroups::none());
+        world.step_with(&Vector::zeros(), &(), &events);
+        let stopped = events.drain_contact_events();
+        assert_eq!(stopped.len(), 1);
+        assert!(stopped[0].stopped());
+
+        world
+            .colliders
+            .set_collision_groups(ball, InteractionGroups::all());
+        world.step_with(&Vector::zeros(), &(), &events);
+        let restarted = events.drain_contact_events();
+        assert_eq!(restarted.len(), 1);
+        assert!(restarted[0].started());
+    }
+
+    #[test]
+    fn support_contacts_detect_the_ground() {
+        let mut world = TestWorld::new();
+        let gravity = Vector::y() * -9.81;
+
+        let ground = world
+            .colliders
+            .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 0.5)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        world.step(&gravity);
+
+        let down = -Vector::y_axis();
+        let supports = world
+            .narrow_phase
+            .support_contacts(handle, &world.bodies, &down, 0.5);
+        assert_eq!(supports.len(), 1);
+        assert_eq!(supports[0].0, ground);
+        assert!(supports[0].1.y > 0.99);
+
+        // Nothing supports the ball against a horizontal gravity.
+        let sideways = Vector::x_axis();
+        assert!(world
+            .narrow_phase
+            .support_contacts(handle, &world.bodies, &sideways, 0.5)
+            .is_empty());
+
+        // The contacts are kept once the ball falls asleep.
+        for _ in 0..300 {
+            world.step(&gravity);
+        }
+
+        assert!(world.bodies[handle].is_sleeping());
+        assert_eq!(world.narrow_phase.active_contact_pairs().count(), 1);
+        let supports = world
+            .narrow_phase
+            .support_contacts(handle, &world.bodies, &down, 0.5);
+        assert_eq!(supports.len(), 1);
+    }
+
+    #[test]
+    fn slow_impacts_do_not_bounce() {
+        fn run(initial_vel: Real) -> Real {
+            let mut world = TestWorld::new();
+
+            world
+                .colliders
+                .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+            let handle = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::y() * 0.5)
+                    .linvel(Vector::y() * initial_vel)
+                    .build(),
+            );
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5).restitution(1.0).build(),
+                handle,
+                &mut world.bodies,
+            );
+
+            for _ in 0..3 {
+                world.step(&(Vector::y() * -9.81));
+            }
+
+            world.bodies[handle].linvel().y
+        }
+
+        // A ball resting on the ground doesn't bounce from the velocity gained from gravity.
+        assert!(run(0.0).abs() < 1.0e-3);
+        // A fast impact still bounces.
+        assert!(run(-5.0) > 4.0);
+    }
+
+    #[test]
+    fn collider_mass_overrides_density() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let handle = bodies.insert(RigidBodyBuilder::dynamic().build());
+        let co1 = ColliderBuilder::ball(0.5).density(10.0).mass(2.0).build();
+        let co2 = ColliderBuilder::ball(0.5).mass(2.0).density(3.0).build();
+        let density_mprops = co2.mass_properties();
+        let unit_mprops = ColliderBuilder::ball(0.5)
+            .density(1.0)
+            .build()
+            .mass_properties();
+
+        assert_eq!(co1.density(), None);
+        assert!((co1.mass_properties().mass() - 2.0).abs() < 1.0e-5);
+        assert_eq!(co2.density(), Some(3.0));
+        assert!((density_mprops.mass() - unit_mprops.mass() * 3.0).abs() < 1.0e-5);
+
+        // The angular inertia is scaled like the mass.
+        let ratio = 2.0 / unit_mprops.mass();
+        let expected = unit_mprops.principal_inertia() * ratio;
+        let error = co1.mass_properties().principal_inertia() - expected;
+        #[cfg(feature = "dim2")]
+        assert!(error.abs() < 1.0e-4);
+        #[cfg(feature = "dim3")]
+        assert!(error.norm() < 1.0e-4);
+
+        colliders.insert_with_parent(co1, handle, &mut bodies);
+        assert!((bodies[handle].mass() - 2.0).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn solver_substeps_keep_resting_contacts_stable() {
+        let mut world = TestWorld::new();
+        world.integration_parameters.num_solver_substeps = 4;
+
+        world
+            .colliders
+            .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 2.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        for _ in 0..200 {
+            world.step(&(Vector::y() * -9.81));
+        }
+
+        // The ball fell, and now rests on the ground.
+        let y = world.bodies[handle].translation().y;
+        assert!((y - 0.5).abs() < 0.05, "unexpected height: {}", y);
+        assert!(world.bodies[handle].linvel().norm() < 0.1);
+    }
+
+    #[test]
+    fn solver_substeps_carry_bodies_on_kinematic_platforms() {
+        let mut world = TestWorld::new();
+        world.integration_parameters.num_solver_substeps = 4;
+
+        #[cfg(feature = "dim2")]
+        let (platform_shape, box_shape) = (
+            ColliderBuilder::cuboid(5.0, 0.5),
+            ColliderBuilder::cuboid(0.5, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (platform_shape, box_shape) = (
+            ColliderBuilder::cuboid(5.0, 0.5, 5.0),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+
+        let platform = world
+            .bodies
+            .insert(RigidBodyBuilder::kinematic_position_based().build());
+        world
+            .colliders
+            .insert_with_parent(platform_shape.build(), platform, &mut world.bodies);
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 1.0)
+                .build(),
+        );
+        world
+            .colliders
+            .insert_with_parent(box_shape.build(), handle, &mut world.bodies);
+
+        // The platform moves up at 1m/s.
+        for i in 0..120 {
+            let platform_y = (i + 1) as Real * world.integration_parameters.dt;
+            world.bodies[platform].set_next_kinematic_translation(Vector::y() * platform_y);
+
+            world.step(&(Vector::y() * -9.81));
+        }
+
+        // The box keeps resting on the platform instead of being launched by it.
+        let gap = world.bodies[handle].translation().y - world.bodies[platform].translation().y;
+        assert!((gap - 1.0).abs() < 0.05, "unexpected gap: {}", gap);
+        assert!((world.bodies[handle].linvel().y - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn detached_collider_stays_in_place() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let handle = bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 2.0)
+                .build(),
+        );
+        colliders.insert_with_parent(ColliderBuilder::ball(0.5).build(), handle, &mut bodies);
+        let prop = colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5)
+                .translation(Vector::x() * 1.0)
+                .build(),
+            handle,
+            &mut bodies,
+        );
+        let mass = bodies[handle].mass();
+        let world_pos = *colliders[prop].position();
+
+        colliders.set_parent(prop, None, &mut bodies);
+
+        assert_eq!(colliders[prop].parent(), None);
+        assert_eq!(*colliders[prop].position(), world_pos);
+        assert!((bodies[handle].mass() - mass / 2.0).abs() < 1.0e-5);
+
+        colliders.set_parent(prop, Some(handle), &mut bodies);
+        assert_eq!(colliders[prop].parent(), Some(handle));
+        assert!((bodies[handle].mass() - mass).abs() < 1.0e-5);
+
+        // Shape changes are taken into account when recomputing the mass properties.
+        colliders
+            .get_mut(prop)
+            .unwrap()
+            .set_shape(crate::geometry::SharedShape::ball(1.0e-3));
+        bodies
+            .get_mut(handle)
+            .unwrap()
+            .recompute_mass_properties_from_colliders(&colliders);
+        assert!((bodies[handle].mass() - mass / 2.0).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn damping_is_consistent_across_substeps() {
+        fn run(num_solver_substeps: usize) -> Real {
+            let mut world = TestWorld::new();
+            world.integration_parameters.num_solver_substeps = num_solver_substeps;
+
+            let handle = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .linvel(Vector::x() * 10.0)
+                    .linear_damping(1.0)
+                    .build(),
+            );
+
+            // Simulate one second.
+            for _ in 0..60 {
+                world.step(&Vector::zeros());
+            }
+
+            world.bodies[handle].linvel().x
+        }
+
+        // The damping approximates an exponential decay.
+        let expected = 10.0 * (-1.0 as Real).exp();
+        assert!((run(1) - expected).abs() < 0.05);
+        assert!((run(4) - expected).abs() < 0.05);
+    }
+
+    #[test]
+    fn contact_hooks_know_the_current_substep() {
+        #[derive(Default)]
+        struct SubstepRecorder(std::sync::Mutex<Vec<(usize, Real)>>);
+        impl PhysicsHooks for SubstepRecorder {
+            fn modify_solver_contacts(&self, context: &mut ContactModificationContext) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((context.substep, context.remaining_time));
+            }
+        }
+
+        let mut world = TestWorld::new();
+        let hooks = SubstepRecorder::default();
+
+        world
+            .colliders
+            .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 0.5)
+                .linvel(Vector::x())
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5)
+                .active_hooks(ActiveHooks::MODIFY_SOLVER_CONTACTS)
+                .build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        world.step_with(&Vector::zeros(), &hooks, &());
+
+        // Once before the first substep, and once after the only substep.
+        let recorded = hooks.0.lock().unwrap().clone();
+        assert_eq!(
+            recorded,
+            vec![(0, world.integration_parameters.dt), (1, 0.0)]
+        );
+    }
+
+    #[test]
+    fn apply_forces_hook_is_called_once_per_ccd_substep() {
+        #[derive(Default)]
+        struct ForceCounter(std::sync::Mutex<usize>);
+        impl PhysicsHooks for ForceCounter {
+            fn on_apply_forces(&self, _bodies: &mut RigidBodySet) {
+                *self.0.lock().unwrap() += 1;
+            }
+        }
+
+        let mut world = TestWorld::new();
+        world.integration_parameters.num_solver_substeps = 4;
+        let hooks = ForceCounter::default();
+
+        let handle = world.bodies.insert(RigidBodyBuilder::dynamic().build());
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        for _ in 0..10 {
+            world.step_with(&(Vector::y() * -9.81), &hooks, &());
+        }
+
+        // Not once per solver substep.
+        assert_eq!(*hooks.0.lock().unwrap(), 10);
+    }
+
+    #[test]
+    fn contact_event_hysteresis_delays_stop_events() {
+        fn stop_step(hysteresis: Real) -> usize {
+            let mut world = TestWorld::new();
+            world.integration_parameters.contact_event_hysteresis = hysteresis;
+            let events = CollectorEventHandler::new();
+
+            world
+                .colliders
+                .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+            // The ball slowly moves away from the ground, by 0.001 per step.
+            let handle = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::y() * 0.5)
+                    .linvel(Vector::y() * 0.06)
+                    .build(),
+            );
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5)
+                    .active_events(ActiveEvents::COLLISION_EVENTS)
+                    .build(),
+                handle,
+                &mut world.bodies,
+            );
+
+            for i in 0..100 {
+                world.step_with(&Vector::zeros(), &(), &events);
+
+                if events.drain_contact_events().iter().any(|e| e.stopped()) {
+                    return i;
+                }
+            }
+
+            panic!("No stop event.");
+        }
+
+        assert!(stop_step(0.0) < 5);
+        let delayed = stop_step(0.01);
+        assert!(delayed >= 10 && delayed < 15, "stopped at step {}", delayed);
+    }
+
+    #[test]
+    fn frozen_island_ignores_impacts() {
+        let mut world = TestWorld::new();
+        world.integration_parameters.min_island_size = 1;
+        let events = CollectorEventHandler::new();
+
+        let ground = world.colliders.insert(
+            ColliderBuilder::halfspace(Vector::y_axis())
+                .active_events(ActiveEvents::COLLISION_EVENTS)
+                .build(),
+        );
+
+        // Two stacks of two balls, far from each other, each hit on its top by a projectile.
+        let mut stacks = vec![];
+        let mut stack_colliders = vec![];
+        let mut projectiles = vec![];
+        for x in [-5.0, 5.0] {
+            let mut stack = vec![];
+            for y in [0.5, 1.5] {
+                let handle = world.bodies.insert(
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::x() * x + Vector::y() * y)
+                        .build(),
+                );
+                stack_colliders.push(
+                    world.colliders.insert_with_parent(
+                        ColliderBuilder::ball(0.5)
+                            .active_events(ActiveEvents::COLLISION_EVENTS)
+                            .build(),
+                        handle,
+                        &mut world.bodies,
+                    ),
+                );
+                stack.push(handle);
+            }
+            stacks.push(stack);
+
+            let handle = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * (x - 3.0) + Vector::y() * 1.5)
+                    .gravity_scale(0.0)
+                    .build(),
+            );
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5).build(),
+                handle,
+                &mut world.bodies,
+            );
+            projectiles.push(handle);
+        }
+
+        for _ in 0..5 {
+            world.step_with(&(Vector::y() * -9.81), &(), &events);
+        }
+
+        // Any contact event between the ground and the balls of the first stack.
+        let frozen_stack_colliders = [ground, stack_colliders[0], stack_colliders[1]];
+        let frozen_stack_events = || {
+            events.drain_contact_events().into_iter().any(|e| {
+                frozen_stack_colliders.contains(&e.collider1())
+                    && frozen_stack_colliders.contains(&e.collider2())
+            })
+        };
+        let _ = frozen_stack_events();
+
+        let island_id = world
+            .islands
+            .island_of(&world.bodies, stacks[0][0])
+            .unwrap();
+        assert_ne!(
+            world.islands.island_of(&world.bodies, stacks[1][0]),
+            Some(island_id)
+        );
+        let mut frozen = world.bodies.freeze_island(&world.islands, island_id);
+        frozen.sort_by_key(|h| h.into_raw_parts());
+        assert_eq!(frozen, stacks[0]);
+        assert!(stacks[0]
+            .iter()
+            .all(|h| world.bodies[*h].is_frozen() && world.bodies[*h].is_dynamic()));
+        assert!(stacks[1].iter().all(|h| !world.bodies[*h].is_frozen()));
+
+        let frozen_top = *world.bodies[stacks[0][1]].translation();
+        let unfrozen_top = *world.bodies[stacks[1][1]].translation();
+        for projectile in &projectiles {
+            world.bodies[*projectile].set_linvel(Vector::x() * 10.0, true);
+        }
+
+        for _ in 0..30 {
+            world.step_with(&(Vector::y() * -9.81), &(), &events);
+        }
+
+        // The frozen stack didn't move, and wasn't woken up, but still stopped its projectile.
+        assert_eq!(*world.bodies[stacks[0][1]].translation(), frozen_top);
+        assert!(stacks[0].iter().all(|h| world.bodies[*h].is_frozen()));
+        assert!(stacks[0]
+            .iter()
+            .all(|h| world.islands.island_of(&world.bodies, *h).is_none()));
+        assert!(world.bodies[projectiles[0]].linvel().x < 1.0e-3);
+        assert!(world.bodies[projectiles[0]].translation().x < -5.0);
+        // The other one was pushed by the impact.
+        assert!((world.bodies[stacks[1][1]].translation() - unfrozen_top).norm() > 0.1);
+        // The contacts inside of the frozen stack were kept.
+        assert!(!frozen_stack_events());
+        assert!(
+            world
+                .narrow_phase
+                .contact_pair(stack_colliders[0], stack_colliders[1])
+                .unwrap()
+                .has_any_active_contact
+        );
+
+        world.bodies.unfreeze_island(&frozen);
+        world.step_with(&(Vector::y() * -9.81), &(), &events);
+        assert!(stacks[0]
+            .iter()
+            .all(|h| world.bodies[*h].is_dynamic() && !world.bodies[*h].is_frozen()));
+        assert!(stacks[0]
+            .iter()
+            .all(|h| world.islands.island_of(&world.bodies, *h).is_some()));
+        assert!(!frozen_stack_events());
+    }
+
+    #[test]
+    fn step_subset_leaves_the_other_bodies_untouched() {
+        let mut world = TestWorld::new();
+        let gravity = Vector::y() * -9.81;
+
+        // The main world: a ball above a parentless ground collider.
+        world
+            .colliders
+            .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+        let main_ball = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 2.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            main_ball,
+            &mut world.bodies,
+        );
+
+        // The preview: a ball above its own fixed ground, far from the main world.
+        let offset = Vector::x() * 100.0;
+        let preview_ground = world
+            .bodies
+            .insert(RigidBodyBuilder::fixed().translation(offset).build());
+        world.colliders.insert_with_parent(
+            ColliderBuilder::halfspace(Vector::y_axis()).build(),
+            preview_ground,
+            &mut world.bodies,
+        );
+        let preview_ball = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(offset + Vector::y() * 2.0)
+                .build(),
+        );
+        let preview_collider = world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            preview_ball,
+            &mut world.bodies,
+        );
+        let mass = world.bodies[preview_ball].mass();
+
+        for _ in 0..100 {
+            world.pipeline.step_subset(
+                &[preview_ground, preview_ball],
+                &gravity,
+                &world.integration_parameters,
+                &mut world.bodies,
+                &mut world.colliders,
+                &world.impulse_joints,
+            );
+        }
+
+        // The preview ball fell on its ground, and its collider followed it.
+        let preview_pos = *world.bodies[preview_ball].translation();
+        assert!((preview_pos.y - 0.5).abs() < 0.05);
+        assert!((preview_pos.x - offset.x).abs() < 1.0e-3);
+        assert_eq!(
+            world.colliders[preview_collider]
+                .position()
+                .translation
+                .vector,
+            preview_pos
+        );
+        assert_eq!(world.bodies[preview_ball].mass(), mass);
+        // The main world didn't move.
+        assert_eq!(*world.bodies[main_ball].translation(), Vector::y() * 2.0);
+        assert_eq!(*world.bodies[main_ball].linvel(), Vector::zeros());
+    }
+
+    #[test]
+    fn step_subset_keeps_the_pipeline_state() {
+        let mut world = TestWorld::new();
+        world.integration_parameters.max_ccd_substeps = 4;
+
+        // A bullet hitting an obstacle, to generate CCD impacts and substeps.
+        let bullet = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 1000.0)
+                .ccd_enabled(true)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.1).build(),
+            bullet,
+            &mut world.bodies,
+        );
+        let obstacle = world.bodies.insert(
+            RigidBodyBuilder::fixed()
+                .translation(Vector::x() * 10.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(1.0).build(),
+            obstacle,
+            &mut world.bodies,
+        );
+
+        // A preview ball, far from the bullet.
+        let preview_ball = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 100.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            preview_ball,
+            &mut world.bodies,
+        );
+
+        world.step(&Vector::zeros());
+        let impacts = world.pipeline.last_ccd_impacts().to_vec();
+        let num_substeps = world.pipeline.counters.ccd.num_substeps;
+        assert!(!impacts.is_empty());
+        assert!(num_substeps > 1);
+
+        world.pipeline.step_subset(
+            &[preview_ball],
+            &(Vector::y() * -9.81),
+            &world.integration_parameters,
+            &mut world.bodies,
+            &mut world.colliders,
+            &world.impulse_joints,
+        );
+
+        assert!(world.bodies[preview_ball].linvel().y < 0.0);
+        assert_eq!(world.pipeline.last_ccd_impacts(), &impacts[..]);
+        assert_eq!(world.pipeline.counters.ccd.num_substeps, num_substeps);
+    }
+
+    #[test]
+    fn appended_rigid_bodies_keep_their_colliders_and_joints() {
+        let mut world = TestWorld::new();
+
+        // Free a slot in the main set.
+        let removed = world.bodies.insert(RigidBodyBuilder::dynamic().build());
+        let kept = world.bodies.insert(RigidBodyBuilder::dynamic().build());
+        world.bodies.remove(
+            removed,
+            &mut world.islands,
+            &mut world.colliders,
+            &mut world.impulse_joints,
+            &mut world.multibody_joints,
+            true,
+        );
+
+        // A chunk simulated separately: two balls linked by a joint.
+        let mut chunk_bodies = RigidBodySet::new();
+        let mut chunk_colliders = ColliderSet::new();
+        let mut chunk_joints = ImpulseJointSet::new();
+        let mut chunk_handles = vec![];
+        for i in 0..2 {
+            let handle = chunk_bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * (i as Real) * 2.0)
+                    .build(),
+            );
+            chunk_colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5).density(2.0).build(),
+                handle,
+                &mut chunk_bodies,
+            );
+            chunk_handles.push(handle);
+        }
+        let joint = SpringJointBuilder::new(2.0, 10.0, 1.0).build();
+        chunk_joints.insert(chunk_handles[0], chunk_handles[1], joint, true);
+        let masses: Vec<_> = chunk_handles
+            .iter()
+            .map(|h| chunk_bodies[*h].mass())
+            .collect();
+
+        let new_handles = world.bodies.append(chunk_bodies);
+        assert_eq!(new_handles.len(), 2);
+        assert_eq!(world.bodies.len(), 3);
+        // The first appended rigid-body reuses the freed slot.
+        assert_eq!(
+            new_handles[&chunk_handles[0]].into_raw_parts().0,
+            removed.into_raw_parts().0
+        );
+        assert!(world.bodies.contains(kept));
+
+        for (_, co) in chunk_colliders.iter() {
+            let parent = new_handles[&co.parent().unwrap()];
+            world
+                .colliders
+                .insert_with_parent(co.clone(), parent, &mut world.bodies);
+        }
+        for (_, joint) in chunk_joints.iter() {
+            world.impulse_joints.insert(
+                new_handles[&joint.body1],
+                new_handles[&joint.body2],
+                joint.data,
+                true,
+            );
+        }
+
+        for (handle, mass) in chunk_handles.iter().zip(masses.iter()) {
+            let new_handle = new_handles[handle];
+            assert_eq!(world.bodies[new_handle].colliders().len(), 1);
+            assert!((world.bodies[new_handle].mass() - mass).abs() < 1.0e-5);
+        }
+
+        world.step(&Vector::zeros());
+
+        for (handle, mass) in chunk_handles.iter().zip(masses.iter()) {
+            assert!((world.bodies[new_handles[handle]].mass() - mass).abs() < 1.0e-5);
+        }
+        assert_eq!(
+            world
+                .impulse_joints
+                .joints_between(
+                    new_handles[&chunk_handles[0]],
+                    new_handles[&chunk_handles[1]]
+                )
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn contact_start_events_carry_the_contact_points() {
+        use crate::geometry::{CollisionEvent, ContactPair};
+        use crate::pipeline::EventHandler;
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct StartContacts(Mutex<Vec<(Point<Real>, Vector<Real>)>>);
+
+        impl EventHandler for StartContacts {
+            fn handle_collision_event(
+                &self,
+                _bodies: &RigidBodySet,
+                colliders: &ColliderSet,
+                event: CollisionEvent,
+                contact_pair: Option<&ContactPair>,
+            ) {
+                if let (CollisionEvent::Started(..), Some(pair)) = (event, contact_pair) {
+                    let (manifold, contact) = pair.find_deepest_contact().unwrap();
+                    let (point, _) = pair
+                        .world_contact_points(colliders, manifold, contact)
+                        .unwrap();
+                    self.0.lock().unwrap().push((point, manifold.data.normal));
+                }
+            }
+        }
+
+        let mut world = TestWorld::new();
+        let events = StartContacts::default();
+
+        world
+            .colliders
+            .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::x() * 3.0 + Vector::y() * 2.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5)
+                .active_events(ActiveEvents::COLLISION_EVENTS)
+                .build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        for _ in 0..100 {
+            world.step_with(&(Vector::y() * -9.81), &(), &events);
+        }
+
+        let contacts = events.0.into_inner().unwrap();
+        assert_eq!(contacts.len(), 1);
+        let (point, normal) = contacts[0];
+        assert!((point.x - 3.0).abs() < 1.0e-3);
+        assert!(point.y.abs() < 0.1);
+        assert!(normal.y.abs() > 0.999);
+    }
+
+    #[cfg(feature = "dim2")]
+    #[test]
+    fn block_solver_keeps_resting_boxes_from_rotating() {
+        // Returns the angular velocity of a box resting on the ground after one step with a
+        // single solver iteration.
+        fn run(use_block_solver: bool) -> Real {
+            let mut world = TestWorld::new();
+            world.integration_parameters.use_block_solver = use_block_solver;
+            world.integration_parameters.max_velocity_iterations = 1;
+            world
+                .integration_parameters
+                .max_velocity_friction_iterations = 1;
+            world.integration_parameters.max_stabilization_iterations = 0;
+
+            let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+            world.colliders.insert_with_parent(
+                ColliderBuilder::cuboid(10.0, 0.1).build(),
+                ground,
+                &mut world.bodies,
+            );
+            let handle = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::y() * 0.6)
+                    .build(),
+            );
+            world.colliders.insert_with_parent(
+                ColliderBuilder::cuboid(0.5, 0.5).build(),
+                handle,
+                &mut world.bodies,
+            );
+
+            world.step(&(Vector::y() * -9.81));
+            world.bodies[handle].angvel()
+        }
+
+        // Solving the two contacts one after the other makes the box rotate, while solving
+        // them simultaneously keeps the symmetry of the impulses.
+        assert!(run(false).abs() > 1.0e-3);
+        assert!(run(true).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn solver_residuals_vanish_once_at_rest() {
+        let mut world = TestWorld::new();
+        world.integration_parameters.force_serial_solver = true;
+        let gravity = Vector::y() * -9.81;
+
+        let ground = world.bodies.insert(RigidBodyBuilder::fixed().build());
+        #[cfg(feature = "dim2")]
+        let ground_shape = ColliderBuilder::cuboid(10.0, 0.1);
+        #[cfg(feature = "dim3")]
+        let ground_shape = ColliderBuilder::cuboid(10.0, 0.1, 10.0);
+        world
+            .colliders
+            .insert_with_parent(ground_shape.build(), ground, &mut world.bodies);
+
+        let ball = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 0.7)
+                .linvel(Vector::y() * -10.0)
+                .can_sleep(false)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            ball,
+            &mut world.bodies,
+        );
+
+        for _ in 0..200 {
+            world.step(&gravity);
+
+            assert!(world.pipeline.counters.velocity_residual() >= 0.0);
+            assert!(world.pipeline.counters.position_residual() >= 0.0);
+        }
+
+        assert!(world.pipeline.counters.velocity_residual() < 1.0e-2);
+        assert!(world.pipeline.counters.position_residual() < 1.0e-2);
+    }
+
+    #[test]
+    fn ccd_is_only_active_for_bodies_with_ccd_enabled() {
+        let mut world = TestWorld::new();
+
+        let bullet = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 1000.0)
+                .ccd_enabled(true)
+                .build(),
+        );
+        let debris = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 1000.0)
+                .build(),
+        );
+
+        for handle in [bullet, debris] {
+            world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1).build(),
+                handle,
+                &mut world.bodies,
+            );
+        }
+
+        // Step once so the bodies are part of the active set.
+        world.step(&Vector::zeros());
+
+        assert!(world.ccd.update_ccd_active_flags(
+            &world.islands,
+            &mut world.bodies,
+            1.0 / 60.0,
+            false
+        ));
+        assert!(world.bodies[bullet].is_ccd_enabled());
+        assert!(world.bodies[bullet].is_ccd_active());
+        assert!(!world.bodies[debris].is_ccd_enabled());
+        assert!(!world.bodies[debris].is_ccd_active());
+
+        world.bodies[bullet].set_ccd_enabled(false);
+        assert!(!world.ccd.update_ccd_active_flags(
+            &world.islands,
+            &mut world.bodies,
+            1.0 / 60.0,
+            false
+        ));
+        assert!(!world.bodies[bullet].is_ccd_active());
+    }
+
+    #[test]
+    fn sleeping_state_is_up_to_date_after_each_step() {
+        let mut world = TestWorld::new();
+
+        let prop = world.bodies.insert(RigidBodyBuilder::dynamic().build());
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            prop,
+            &mut world.bodies,
+        );
+        assert!(!world.bodies.is_sleeping(prop));
+
+        let mut fell_asleep = false;
+
+        for _ in 0..200 {
+            world.step(&Vector::zeros());
+
+            let is_active = world.islands.active_dynamic_bodies().contains(&prop);
+            assert_eq!(world.bodies.is_sleeping(prop), !is_active);
+            assert_eq!(
+                world.bodies.is_sleeping(prop),
+                world.bodies[prop].is_sleeping()
+            );
+            fell_asleep = fell_asleep || world.bodies.is_sleeping(prop);
+        }
+
+        assert!(fell_asleep);
+
+        world.bodies.remove(
+            prop,
+            &mut world.islands,
+            &mut world.colliders,
+            &mut world.impulse_joints,
+            &mut world.multibody_joints,
+            true,
+        );
+        assert!(!world.bodies.is_sleeping(prop));
+    }
+
+    #[test]
+    fn contact_hooks_can_override_friction_and_tangent_velocity() {
+        struct Belt {
+            handle: ColliderHandle,
+            friction: Real,
+        }
+        impl PhysicsHooks for Belt {
+            fn modify_solver_contacts(&self, context: &mut ContactModificationContext) {
+                // The belt moves along +X, so the other collider must move along +X
+                // relative to it.
+                let speed = if context.collider1 == self.handle {
+                    2.0
+                } else {
+                    -2.0
+                };
+                context.set_friction(self.friction);
+                context.set_tangent_velocity(Vector::x() * speed);
+            }
+        }
+
+        fn run(friction: Real) -> Vector<Real> {
+            let mut world = TestWorld::new();
+            let gravity = Vector::y() * -9.81;
+
+            #[cfg(feature = "dim2")]
+            let (belt_shape, box_shape) = (
+                ColliderBuilder::cuboid(100.0, 0.1),
+                ColliderBuilder::cuboid(0.5, 0.5),
+            );
+            #[cfg(feature = "dim3")]
+            let (belt_shape, box_shape) = (
+                ColliderBuilder::cuboid(100.0, 0.1, 100.0),
+                ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+            );
+
+            let belt = world.colliders.insert(
+                belt_shape
+                    .active_hooks(ActiveHooks::MODIFY_SOLVER_CONTACTS)
+                    .build(),
+            );
+            let cargo = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::y() * 0.6)
+                    .build(),
+            );
+            world
+                .colliders
+                .insert_with_parent(box_shape.build(), cargo, &mut world.bodies);
+            let hooks = Belt {
+                handle: belt,
+                friction,
+            };
+
+            for _ in 0..120 {
+                world.step_with(&gravity, &hooks, &());
+            }
+
+            *world.bodies[cargo].linvel()
+        }
+
+        // The conveyor belt drags the box along.
+        assert!((run(0.5).x - 2.0).abs() < 0.1);
+        // Without friction, the belt slides under the box.
+        assert!(run(0.0).x.abs() < 1.0e-3);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_solver_is_reproducible() {
+        fn run(num_threads: Option<usize>) -> Vec<Vector<Real>> {
+            let mut world = TestWorld::new();
+            world.integration_parameters.num_threads = num_threads;
+            world.integration_parameters.min_island_size = 1;
+            let gravity = Vector::y() * -9.81;
+
+            // Several independent islands, each made of a few balls falling on a fixed ball.
+            let mut handles = vec![];
+            for i in 0..8 {
+                let x = Vector::x() * (i as Real) * 10.0;
+                let ground = world
+                    .bodies
+                    .insert(RigidBodyBuilder::fixed().translation(x).build());
+                world.colliders.insert_with_parent(
+                    ColliderBuilder::ball(2.0).build(),
+                    ground,
+                    &mut world.bodies,
+                );
+
+                for j in 0..4 {
+                    let pos = x + Vector::x() * 0.1 * (j as Real) + Vector::y() * (3.0 + j as Real);
+                    let rb = RigidBodyBuilder::dynamic().translation(pos).build();
+                    let handle = world.bodies.insert(rb);
+                    world.colliders.insert_with_parent(
+                        ColliderBuilder::ball(0.5).build(),
+                        handle,
+                        &mut world.bodies,
+                    );
+                    handles.push(handle);
+                }
+            }
+
+            for _ in 0..100 {
+                world.step(&gravity);
+            }
+
+            assert_eq!(
+                world
+                    .pipeline
+                    .thread_pool
+                    .as_ref()
+                    .map(|pool| pool.current_num_threads()),
+                num_threads
+            );
+            handles
+                .iter()
+                .map(|h| *world.bodies[*h].translation())
+                .collect()
+        }
+
+        // The islands are solved concurrently, in any order.
+        let reference = run(None);
+        assert_eq!(run(None), reference);
+        // The results don't depend on the number of threads either.
+        assert_eq!(run(Some(1)), reference);
+        assert_eq!(run(Some(2)), reference);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn forced_serial_solver_matches_the_parallel_solver() {
+        use crate::math::Isometry;
+
+        fn run(force_serial_solver: bool) -> Vec<Isometry<Real>> {
+            let mut world = TestWorld::new();
+            world.integration_parameters.force_serial_solver = force_serial_solver;
+            world.integration_parameters.min_island_size = 1;
+            let gravity = Vector::y() * -9.81;
+
+            // Several independent islands, each made of a ball rolling off a fixed ball. Each
+            // island only has one contact constraint, so both solvers solve the exact same
+            // constraints in the same order.
+            let mut handles = vec![];
+            for i in 0..8 {
+                let x = Vector::x() * (i as Real) * 10.0;
+                let ground = world
+                    .bodies
+                    .insert(RigidBodyBuilder::fixed().translation(x).build());
+                world.colliders.insert_with_parent(
+                    ColliderBuilder::ball(2.0).build(),
+                    ground,
+                    &mut world.bodies,
+                );
+
+                let pos = x + Vector::x() * 0.1 * (i as Real + 1.0) + Vector::y() * 3.0;
+                let handle = world
+                    .bodies
+                    .insert(RigidBodyBuilder::dynamic().translation(pos).build());
+                world.colliders.insert_with_parent(
+                    ColliderBuilder::ball(0.5).build(),
+                    handle,
+                    &mut world.bodies,
+                );
+                handles.push(handle);
+            }
+
+            for _ in 0..100 {
+                world.step(&gravity);
+            }
+
+            handles
+                .iter()
+                .map(|h| *world.bodies[*h].position())
+                .collect()
+        }
+
+        let parallel = run(false);
+        assert!(parallel.iter().all(|pos| pos.translation.vector.y < 3.0));
+        assert_eq!(run(true), parallel);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn forced_serial_solver_skips_the_parallel_solver() {
+        let mut world = TestWorld::new();
+        world.integration_parameters.force_serial_solver = true;
+
+        world
+            .colliders
+            .insert(ColliderBuilder::halfspace(Vector::y_axis()).build());
+        let handle = world.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 2.0)
+                .build(),
+        );
+        world.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        for _ in 0..100 {
+            world.step(&(Vector::y() * -9.81));
+        }
+
+        assert!(world.pipeline.parallel_solvers.is_empty());
+        assert!(!world.pipeline.solvers.is_empty());
+        // The ball rests on the ground.
+        assert!((world.bodies[handle].translation().y - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn incompatible_solver_groups_report_contacts_without_impulses() {
+        let mut world = TestWorld::new();
+        let events = CollectorEventHandler::new();
+
+        let mut add_ball = |x: Real, vx: Real, groups: InteractionGroups| {
+            let handle = world.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * x)
+                    .linvel(Vector::x() * vx)
+                    .solver_groups(groups)
+                    .build(),
+            );
+            let collider = world.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5)
+                    .active_events(ActiveEvents::COLLISION_EVENTS)
+                    .build(),
+                handle,
+                &mut world.bodies,
+            );
+            (handle, collider)
+        };
+
+        let (moving, moving_co) = add_ball(-1.0, 2.0, InteractionGroups::new(0b01, 0b01));
+        let (idle, idle_co) = add_ball(0.5, 0.0, InteractionGroups::new(0b10, 0b10));
+
+        for _ in 0..120 {
+            world.step_with(&Vector::zeros(), &(), &events);
+        }
+
+        // The contact is still detected and reported, but never solved.
+        let started = events.drain_contact_events().into_iter().any(|event| {
+            event.started()
+                && ((event.collider1() == moving_co && event.collider2() == idle_co)
+                    || (event.collider1() == idle_co && event.collider2() == moving_co))
+        });
+        assert!(started);
+
+        // Neither body received an impulse, so the moving ball went through the idle one.
+        assert_eq!(*world.bodies[moving].linvel(), Vector::x() * 2.0);
+        assert_eq!(*world.bodies[idle].linvel(), Vector::zeros());
+        assert!(world.bodies[moving].translation().x > world.bodies[idle].translation().x + 1.0);
+    }
 }
@@ -304,6 +304,59 @@ impl QueryPipeline {
         self.qbvh.traverse_best_first(&mut visitor).map(|h| h.1)
     }
 
+    /// Find the closest intersection between a ray and the colliders attached to active rigid-bodies.
+    ///
+    /// Colliders without parent, or attached to a sleeping or fixed rigid-body, are ignored.
+    ///
+    /// # Parameters
+    /// * `islands` - The island manager containing the set of active rigid-bodies.
+    /// * `bodies` - The set of rigid-bodies the colliders are attached to.
+    /// * `colliders` - The set of colliders taking part in this pipeline.
+    /// * `ray`: the ray to cast.
+    /// * `max_toi`: the maximum time-of-impact that can be reported by this cast. This effectively
+    ///   limits the length of the ray to `ray.dir.norm() * max_toi`. Use `Real::MAX` for an unbounded ray.
+    /// * `solid`: if this is `true` an impact at time 0.0 (i.e. at the ray origin) is returned if
+    ///            it starts inside of a shape. If this `false` then the ray will hit the shape's boundary
+    ///            even if its starts inside of it.
+    /// * `query_groups`: the interaction groups which will be tested against the collider's `contact_group`
+    ///                   to determine if it should be taken into account by this query.
+    /// * `filter`: a more fine-grained filter. A collider is taken into account by this query if
+    ///             its `contact_group` is compatible with the `query_groups`, if its parent is active,
+    ///             and if this `filter` is either `None` or returns `true`.
+    pub fn cast_ray_active_only(
+        &self,
+        islands: &IslandManager,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+        query_groups: InteractionGroups,
+        filter: Option<&dyn Fn(ColliderHandle) -> bool>,
+    ) -> Option<(ColliderHandle, Real)> {
+        let active_filter = |handle: ColliderHandle| {
+            let parent_is_active = colliders
+                .get(handle)
+                .and_then(|co| co.parent.as_ref())
+                .and_then(|parent| {
+                    let rb = bodies.get(parent.handle)?;
+                    Some(islands.is_in_active_set(parent.handle, &rb.ids))
+                })
+                .unwrap_or(false);
+
+            parent_is_active && filter.map(|f| f(handle)).unwrap_or(true)
+        };
+
+        self.cast_ray(
+            colliders,
+            ray,
+            max_toi,
+            solid,
+            query_groups,
+            Some(&active_filter),
+        )
+    }
+
     /// Find the closest intersection between a ray and a set of collider.
     ///
     /// # Parameters
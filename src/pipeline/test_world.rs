@@ -0,0 +1,69 @@
+//! A default physics world shared by the tests of the pipelines.
+
+use crate::dynamics::{
+    CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
+    RigidBodySet,
+};
+use crate::geometry::{BroadPhase, ColliderSet, NarrowPhase};
+use crate::math::{Real, Vector};
+use crate::pipeline::{EventHandler, PhysicsHooks, PhysicsPipeline};
+
+/// All the structures needed to step a physics world, initialized with their default values.
+pub(crate) struct TestWorld {
+    pub pipeline: PhysicsPipeline,
+    pub integration_parameters: IntegrationParameters,
+    pub islands: IslandManager,
+    pub broad_phase: BroadPhase,
+    pub narrow_phase: NarrowPhase,
+    pub bodies: RigidBodySet,
+    pub colliders: ColliderSet,
+    pub impulse_joints: ImpulseJointSet,
+    pub multibody_joints: MultibodyJointSet,
+    pub ccd: CCDSolver,
+}
+
+impl TestWorld {
+    /// Creates an empty world with the default integration parameters.
+    pub fn new() -> Self {
+        Self {
+            pipeline: PhysicsPipeline::new(),
+            integration_parameters: IntegrationParameters::default(),
+            islands: IslandManager::new(),
+            broad_phase: BroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            bodies: RigidBodySet::new(),
+            colliders: ColliderSet::new(),
+            impulse_joints: ImpulseJointSet::new(),
+            multibody_joints: MultibodyJointSet::new(),
+            ccd: CCDSolver::new(),
+        }
+    }
+
+    /// Runs one timestep, without physics hooks nor event handler.
+    pub fn step(&mut self, gravity: &Vector<Real>) {
+        self.step_with(gravity, &(), &())
+    }
+
+    /// Runs one timestep with the given physics hooks and event handler.
+    pub fn step_with(
+        &mut self,
+        gravity: &Vector<Real>,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+    ) {
+        self.pipeline.step(
+            gravity,
+            &self.integration_parameters,
+            &mut self.islands,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.impulse_joints,
+            &mut self.multibody_joints,
+            &mut self.ccd,
+            hooks,
+            events,
+        );
+    }
+}
@@ -73,6 +73,31 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
             // The body's status changed. We need to make sure
             // it is on the correct active set.
             if let Some(islands) = islands.as_deref_mut() {
+                if changes.contains(RigidBodyChanges::ENABLED_OR_DISABLED) {
+                    if rb.enabled {
+                        // Add the re-enabled body back to its active set.
+                        if rb.is_dynamic() {
+                            // Make sure the code handling sleeping change adds the body to
+                            // the active_dynamic_set if it was awake before being disabled.
+                            changes.set(RigidBodyChanges::SLEEP, true);
+                        } else if rb.is_kinematic()
+                            && islands.active_kinematic_set.get(ids.active_set_id) != Some(handle)
+                        {
+                            ids.active_set_id = islands.active_kinematic_set.len();
+                            islands.active_kinematic_set.push(*handle);
+                        }
+                    } else if islands.active_dynamic_set.get(ids.active_set_id) == Some(handle) {
+                        // Remove the disabled body from its active set.
+                        islands.active_dynamic_set.swap_remove(ids.active_set_id);
+                        final_action =
+                            Some((FinalAction::UpdateActiveDynamicSetId, ids.active_set_id));
+                    } else if islands.active_kinematic_set.get(ids.active_set_id) == Some(handle) {
+                        islands.active_kinematic_set.swap_remove(ids.active_set_id);
+                        final_action =
+                            Some((FinalAction::UpdateActiveKinematicSetId, ids.active_set_id));
+                    }
+                }
+
                 if changes.contains(RigidBodyChanges::TYPE) {
                     match rb.body_type {
                         RigidBodyType::Dynamic => {
@@ -105,12 +130,47 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                             }
 
                             // Add to the active kinematic set.
-                            if islands.active_kinematic_set.get(ids.active_set_id) != Some(handle) {
+                            if rb.enabled
+                                && islands.active_kinematic_set.get(ids.active_set_id)
+                                    != Some(handle)
+                            {
                                 ids.active_set_id = islands.active_kinematic_set.len();
                                 islands.active_kinematic_set.push(*handle);
                             }
                         }
-                        RigidBodyType::Fixed => {}
+                        RigidBodyType::Fixed => {
+                            // Remove from the active sets so it is no longer simulated.
+                            if islands.active_dynamic_set.get(ids.active_set_id) == Some(handle) {
+                                islands.active_dynamic_set.swap_remove(ids.active_set_id);
+                                final_action = Some((
+                                    FinalAction::UpdateActiveDynamicSetId,
+                                    ids.active_set_id,
+                                ));
+                            } else if islands.active_kinematic_set.get(ids.active_set_id)
+                                == Some(handle)
+                            {
+                                islands.active_kinematic_set.swap_remove(ids.active_set_id);
+                                final_action = Some((
+                                    FinalAction::UpdateActiveKinematicSetId,
+                                    ids.active_set_id,
+                                ));
+                            }
+                        }
+                    }
+                }
+
+                if changes.contains(RigidBodyChanges::FROZEN) {
+                    if rb.is_frozen() {
+                        // Remove the frozen body from the active dynamic set.
+                        if islands.active_dynamic_set.get(ids.active_set_id) == Some(handle) {
+                            islands.active_dynamic_set.swap_remove(ids.active_set_id);
+                            final_action =
+                                Some((FinalAction::UpdateActiveDynamicSetId, ids.active_set_id));
+                        }
+                    } else if rb.is_dynamic() {
+                        // Add the unfrozen body back to the active dynamic set.
+                        activation.wake_up(true);
+                        changes.set(RigidBodyChanges::SLEEP, true);
                     }
                 }
 
@@ -122,6 +182,7 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                         .update_positions(colliders, modified_colliders, &rb.pos.position);
 
                     if rb.is_kinematic()
+                        && rb.enabled
                         && islands.active_kinematic_set.get(ids.active_set_id) != Some(handle)
                     {
                         ids.active_set_id = islands.active_kinematic_set.len();
@@ -134,6 +195,8 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                 if changes.contains(RigidBodyChanges::SLEEP)
                     && !activation.sleeping // May happen if the body was put to sleep manually.
                     && rb.is_dynamic() // Only dynamic bodies are in the active dynamic set.
+                    && rb.enabled // Disabled bodies are never in the active dynamic set.
+                    && !rb.is_frozen() // Frozen bodies are never in the active dynamic set.
                     && islands.active_dynamic_set.get(ids.active_set_id) != Some(handle)
                 {
                     ids.active_set_id = islands.active_dynamic_set.len(); // This will handle the case where the activation_channel contains duplicates.
@@ -151,6 +214,7 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
 
             if changes.contains(RigidBodyChanges::DOMINANCE)
                 || changes.contains(RigidBodyChanges::TYPE)
+                || changes.contains(RigidBodyChanges::FROZEN)
             {
                 for handle in rb.colliders.0.iter() {
                     let co = colliders.index_mut_internal(*handle);
@@ -163,6 +227,17 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                 }
             }
 
+            if changes.contains(RigidBodyChanges::ENABLED_OR_DISABLED) {
+                for handle in rb.colliders.0.iter() {
+                    let co = colliders.index_mut_internal(*handle);
+                    if !co.changes.contains(ColliderChanges::MODIFIED) {
+                        modified_colliders.push(*handle);
+                    }
+
+                    co.changes |= ColliderChanges::MODIFIED | ColliderChanges::PARENT_ENABLED;
+                }
+            }
+
             rb.changes = RigidBodyChanges::empty();
             rb.ids = ids;
             rb.activation = activation;
@@ -7,7 +7,7 @@ use rapier::dynamics::{
     CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
     RigidBodySet,
 };
-use rapier::geometry::{BroadPhase, ColliderSet, NarrowPhase};
+use rapier::geometry::{BroadPhase, BroadPhaseAlgorithm, ColliderSet, NarrowPhase};
 use rapier::math::{Real, Vector};
 use rapier::pipeline::{ChannelEventCollector, PhysicsHooks, PhysicsPipeline, QueryPipeline};
 
@@ -169,6 +169,7 @@ impl Harness {
 
         self.physics.islands = IslandManager::new();
         self.physics.broad_phase = BroadPhase::new();
+        self.physics.broad_phase_grid = None;
         self.physics.narrow_phase = NarrowPhase::new();
         self.state.timestep_id = 0;
         self.state.time = 0.0;
@@ -201,11 +202,16 @@ impl Harness {
             let physics = &mut self.physics;
             let event_handler = &self.event_handler;
             self.state.thread_pool.install(|| {
+                let broad_phase: &mut dyn BroadPhaseAlgorithm = match &mut physics.broad_phase_grid
+                {
+                    Some(grid) => grid,
+                    None => &mut physics.broad_phase,
+                };
                 physics.pipeline.step(
                     &physics.gravity,
                     &physics.integration_parameters,
                     &mut physics.islands,
-                    &mut physics.broad_phase,
+                    broad_phase,
                     &mut physics.narrow_phase,
                     &mut physics.bodies,
                     &mut physics.colliders,
@@ -219,20 +225,27 @@ impl Harness {
         }
 
         #[cfg(not(feature = "parallel"))]
-        self.physics.pipeline.step(
-            &self.physics.gravity,
-            &self.physics.integration_parameters,
-            &mut self.physics.islands,
-            &mut self.physics.broad_phase,
-            &mut self.physics.narrow_phase,
-            &mut self.physics.bodies,
-            &mut self.physics.colliders,
-            &mut self.physics.impulse_joints,
-            &mut self.physics.multibody_joints,
-            &mut self.physics.ccd_solver,
-            &*self.physics.hooks,
-            &self.event_handler,
-        );
+        {
+            let broad_phase: &mut dyn BroadPhaseAlgorithm = match &mut self.physics.broad_phase_grid
+            {
+                Some(grid) => grid,
+                None => &mut self.physics.broad_phase,
+            };
+            self.physics.pipeline.step(
+                &self.physics.gravity,
+                &self.physics.integration_parameters,
+                &mut self.physics.islands,
+                broad_phase,
+                &mut self.physics.narrow_phase,
+                &mut self.physics.bodies,
+                &mut self.physics.colliders,
+                &mut self.physics.impulse_joints,
+                &mut self.physics.multibody_joints,
+                &mut self.physics.ccd_solver,
+                &*self.physics.hooks,
+                &self.event_handler,
+            );
+        }
 
         self.physics.query_pipeline.update(
             &self.physics.islands,
@@ -3,7 +3,7 @@ use rapier::dynamics::{
     CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
     RigidBodySet,
 };
-use rapier::geometry::{BroadPhase, ColliderSet, CollisionEvent, NarrowPhase};
+use rapier::geometry::{BroadPhase, BroadPhaseGrid, ColliderSet, CollisionEvent, NarrowPhase};
 use rapier::math::{Real, Vector};
 use rapier::pipeline::{PhysicsHooks, PhysicsPipeline, QueryPipeline};
 
@@ -73,6 +73,8 @@ impl PhysicsSnapshot {
 pub struct PhysicsState {
     pub islands: IslandManager,
     pub broad_phase: BroadPhase,
+    /// If set, this grid broad-phase is used instead of `broad_phase`.
+    pub broad_phase_grid: Option<BroadPhaseGrid>,
     pub narrow_phase: NarrowPhase,
     pub bodies: RigidBodySet,
     pub colliders: ColliderSet,
@@ -91,6 +93,7 @@ impl PhysicsState {
         Self {
             islands: IslandManager::new(),
             broad_phase: BroadPhase::new(),
+            broad_phase_grid: None,
             narrow_phase: NarrowPhase::new(),
             bodies: RigidBodySet::new(),
             colliders: ColliderSet::new(),
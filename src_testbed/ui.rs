@@ -207,6 +207,8 @@ fn profiling_string(counters: &Counters) -> String {
 Collision detection: {:.2}ms
 |_ Broad-phase: {:.2}ms
    Narrow-phase: {:.2}ms
+   |_ Contacts: {:.2}ms
+      Intersections: {:.2}ms
 Island computation: {:.2}ms
 Solver: {:.2}ms
 |_ Velocity assembly: {:.2}ms
@@ -224,6 +226,8 @@ CCD: {:.2}ms
         counters.collision_detection_time(),
         counters.broad_phase_time(),
         counters.narrow_phase_time(),
+        counters.contacts_time(),
+        counters.intersections_time(),
         counters.island_construction_time(),
         counters.solver_time(),
         counters.solver.velocity_assembly_time.time(),